@@ -0,0 +1,6 @@
+//! Compiles the gRPC protobuf schema with tonic-build at build time.
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    tonic_build::compile_protos("proto/agent.proto")?;
+    Ok(())
+}
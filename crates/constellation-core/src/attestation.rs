@@ -0,0 +1,266 @@
+//! DICE-style attestation certificate chains for signer provenance.
+//!
+//! An [`McpSignature`](crate::models::mcp::McpSignature) carries only a bare
+//! `signer`/`key_id`, with no way to prove the key belongs to a legitimate
+//! agent. An [`AttestationChain`] is a sequence of signed certificates where
+//! each link certifies the next key's public value plus an agent identity and
+//! validity window — a layered chain terminating in a trusted root, as in
+//! DICE / explicit-key cert chains.
+
+use chrono::{DateTime, Utc};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+
+use crate::models::mcp::McpSecureEnvelope;
+
+/// A single link in an attestation chain, signed by its issuer (the parent).
+#[derive(Debug, Clone)]
+pub struct AttestationCertificate {
+    /// Agent identity this certificate attests.
+    pub agent_id: String,
+    /// Public key of the subject (the key certified by this link).
+    pub subject_public_key: VerifyingKey,
+    /// When the certificate became valid.
+    pub issued_at: DateTime<Utc>,
+    /// When the certificate expires.
+    pub expires_at: DateTime<Utc>,
+    /// Whether this link has been revoked.
+    pub revoked: bool,
+    /// Signature over the certified fields, produced by the issuer's key.
+    pub signature: Signature,
+}
+
+impl AttestationCertificate {
+    /// Canonical bytes signed by the issuer over this certificate's fields.
+    fn signing_input(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(self.agent_id.as_bytes());
+        buf.extend_from_slice(self.subject_public_key.as_bytes());
+        buf.extend_from_slice(self.issued_at.to_rfc3339().as_bytes());
+        buf.extend_from_slice(self.expires_at.to_rfc3339().as_bytes());
+        buf
+    }
+
+    /// Issue and sign a certificate for `subject` using the issuer's key.
+    pub fn issue(
+        issuer: &SigningKey,
+        agent_id: impl Into<String>,
+        subject_public_key: VerifyingKey,
+        issued_at: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+    ) -> Self {
+        let mut cert = Self {
+            agent_id: agent_id.into(),
+            subject_public_key,
+            issued_at,
+            expires_at,
+            revoked: false,
+            signature: Signature::from_bytes(&[0u8; 64]),
+        };
+        cert.signature = issuer.sign(&cert.signing_input());
+        cert
+    }
+}
+
+/// An ordered chain of certificates from a trusted root down to the leaf key.
+#[derive(Debug, Clone)]
+pub struct AttestationChain {
+    /// Certificates ordered root → leaf. `links[0]` is signed by a trust root.
+    pub links: Vec<AttestationCertificate>,
+}
+
+/// Validation strictness, selected from the context's authentication method.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CertificateChainPolicy {
+    /// Walk the chain and check signatures, expiry, and revocation.
+    Base,
+    /// Mutual-TLS-equivalent strictness: additionally require every link to
+    /// attest the same agent identity as the leaf.
+    MutualTls,
+}
+
+/// The identity proven by a successfully validated chain.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttestedIdentity {
+    /// The agent id attested by the leaf certificate.
+    pub agent_id: String,
+}
+
+/// Error returned while validating an attestation chain.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AttestationError {
+    /// The chain was empty.
+    EmptyChain,
+    /// The root link was not signed by any trusted root key.
+    UntrustedRoot,
+    /// A link's signature did not verify against its issuer's key.
+    BadLinkSignature { index: usize },
+    /// A link was expired at validation time.
+    Expired { index: usize },
+    /// A link had been revoked.
+    Revoked { index: usize },
+    /// Under the mutual-TLS policy, a link attested a different agent id.
+    IdentityMismatch { index: usize },
+    /// The attested identity did not match the envelope sender.
+    SenderMismatch { attested: String, sender: String },
+}
+
+impl std::fmt::Display for AttestationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AttestationError::EmptyChain => write!(f, "attestation chain is empty"),
+            AttestationError::UntrustedRoot => write!(f, "chain root is not a trusted root"),
+            AttestationError::BadLinkSignature { index } => {
+                write!(f, "bad signature on chain link {index}")
+            }
+            AttestationError::Expired { index } => write!(f, "chain link {index} is expired"),
+            AttestationError::Revoked { index } => write!(f, "chain link {index} is revoked"),
+            AttestationError::IdentityMismatch { index } => {
+                write!(f, "chain link {index} attests a different identity")
+            }
+            AttestationError::SenderMismatch { attested, sender } => {
+                write!(f, "attested identity {attested} does not match sender {sender}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for AttestationError {}
+
+impl AttestationChain {
+    /// Walk the chain from root to leaf, verifying each link's signature with
+    /// its parent's key, rejecting expired or revoked links, and returning the
+    /// attested leaf identity.
+    pub fn verify_chain(
+        &self,
+        trust_roots: &[VerifyingKey],
+        policy: CertificateChainPolicy,
+        now: DateTime<Utc>,
+    ) -> Result<AttestedIdentity, AttestationError> {
+        let leaf = self.links.last().ok_or(AttestationError::EmptyChain)?;
+
+        // The root link must be signed by one of the trusted root keys.
+        let root = &self.links[0];
+        let root_trusted = trust_roots
+            .iter()
+            .any(|rk| rk.verify(&root.signing_input(), &root.signature).is_ok());
+        if !root_trusted {
+            return Err(AttestationError::UntrustedRoot);
+        }
+
+        for (index, link) in self.links.iter().enumerate() {
+            if link.revoked {
+                return Err(AttestationError::Revoked { index });
+            }
+            if now >= link.expires_at || now < link.issued_at {
+                return Err(AttestationError::Expired { index });
+            }
+            // Every link except the root is signed by its parent's subject key.
+            if index > 0 {
+                let parent = &self.links[index - 1];
+                parent
+                    .subject_public_key
+                    .verify(&link.signing_input(), &link.signature)
+                    .map_err(|_| AttestationError::BadLinkSignature { index })?;
+            }
+            if policy == CertificateChainPolicy::MutualTls && link.agent_id != leaf.agent_id {
+                return Err(AttestationError::IdentityMismatch { index });
+            }
+        }
+
+        Ok(AttestedIdentity {
+            agent_id: leaf.agent_id.clone(),
+        })
+    }
+}
+
+impl McpSecureEnvelope {
+    /// Verify the signature *and* that the attestation chain proves an identity
+    /// matching the envelope's `sender`. Returns the attested identity on success.
+    pub fn verify_attested(
+        &self,
+        verifying_key: &VerifyingKey,
+        chain: &AttestationChain,
+        trust_roots: &[VerifyingKey],
+        policy: CertificateChainPolicy,
+        now: DateTime<Utc>,
+    ) -> Result<AttestedIdentity, Box<dyn std::error::Error>> {
+        self.verify(verifying_key, None)?;
+        let identity = chain.verify_chain(trust_roots, policy, now)?;
+        if identity.agent_id != self.sender {
+            return Err(Box::new(AttestationError::SenderMismatch {
+                attested: identity.agent_id,
+                sender: self.sender.clone(),
+            }));
+        }
+        Ok(identity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(seed: u8) -> SigningKey {
+        SigningKey::from_bytes(&[seed; 32])
+    }
+
+    #[test]
+    fn test_valid_chain_returns_leaf_identity() {
+        let now = Utc::now();
+        let expires = now + chrono::Duration::days(1);
+        let root = key(1);
+        let leaf = key(2);
+
+        let cert = AttestationCertificate::issue(
+            &root,
+            "agent-leaf",
+            leaf.verifying_key(),
+            now,
+            expires,
+        );
+        let chain = AttestationChain { links: vec![cert] };
+
+        let identity = chain
+            .verify_chain(&[root.verifying_key()], CertificateChainPolicy::Base, now)
+            .unwrap();
+        assert_eq!(identity.agent_id, "agent-leaf");
+    }
+
+    #[test]
+    fn test_untrusted_root_rejected() {
+        let now = Utc::now();
+        let root = key(1);
+        let leaf = key(2);
+        let cert = AttestationCertificate::issue(
+            &root,
+            "agent-leaf",
+            leaf.verifying_key(),
+            now,
+            now + chrono::Duration::days(1),
+        );
+        let chain = AttestationChain { links: vec![cert] };
+        assert_eq!(
+            chain.verify_chain(&[key(9).verifying_key()], CertificateChainPolicy::Base, now),
+            Err(AttestationError::UntrustedRoot)
+        );
+    }
+
+    #[test]
+    fn test_expired_link_rejected() {
+        let now = Utc::now();
+        let root = key(1);
+        let leaf = key(2);
+        let cert = AttestationCertificate::issue(
+            &root,
+            "agent-leaf",
+            leaf.verifying_key(),
+            now - chrono::Duration::days(2),
+            now - chrono::Duration::days(1),
+        );
+        let chain = AttestationChain { links: vec![cert] };
+        assert_eq!(
+            chain.verify_chain(&[root.verifying_key()], CertificateChainPolicy::Base, now),
+            Err(AttestationError::Expired { index: 0 })
+        );
+    }
+}
@@ -0,0 +1,281 @@
+//! Pluggable audit sink subsystem.
+//!
+//! [`AuditLogging`](crate::models::mcp::AuditLogging) only records which events
+//! *would* be logged; this module emits them. An [`AuditSink`] receives
+//! structured [`AuditEntry`] records, and [`McpSecurityContext`] filters the
+//! events it generates during sign/verify/key operations against its configured
+//! `events_to_log` before dispatching to the sink.
+
+use std::collections::VecDeque;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::models::mcp::{
+    AuditEventType, AuditSeverity, McpSecurityContext,
+};
+
+/// A structured audit record produced by a security operation.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AuditEntry {
+    /// Category of the event.
+    pub event_type: AuditEventType,
+    /// Severity of the event.
+    pub severity: AuditSeverity,
+    /// The actor (agent id / principal) that triggered the event.
+    pub actor: String,
+    /// The resource acted upon.
+    pub resource: String,
+    /// The action performed.
+    pub action: String,
+    /// Whether the operation succeeded.
+    pub success: bool,
+    /// When the event occurred.
+    pub timestamp: DateTime<Utc>,
+    /// Envelope message id, when the event concerns a specific envelope.
+    pub message_id: Option<Uuid>,
+}
+
+impl AuditEntry {
+    /// Create an entry stamped at the current time.
+    pub fn new(
+        event_type: AuditEventType,
+        severity: AuditSeverity,
+        actor: impl Into<String>,
+        resource: impl Into<String>,
+        action: impl Into<String>,
+        success: bool,
+    ) -> Self {
+        Self {
+            event_type,
+            severity,
+            actor: actor.into(),
+            resource: resource.into(),
+            action: action.into(),
+            success,
+            timestamp: Utc::now(),
+            message_id: None,
+        }
+    }
+
+    /// Attach an envelope message id.
+    pub fn with_message_id(mut self, message_id: Uuid) -> Self {
+        self.message_id = Some(message_id);
+        self
+    }
+}
+
+/// A destination that persists or forwards audit entries.
+pub trait AuditSink: Send + Sync {
+    /// Record a single audit entry.
+    fn record(&self, entry: AuditEntry);
+}
+
+/// In-memory ring buffer sink, primarily for tests and diagnostics.
+pub struct RingBufferSink {
+    capacity: usize,
+    entries: Mutex<VecDeque<AuditEntry>>,
+}
+
+impl RingBufferSink {
+    /// Create a ring buffer holding at most `capacity` entries.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Mutex::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    /// Snapshot the currently buffered entries, oldest first.
+    pub fn entries(&self) -> Vec<AuditEntry> {
+        self.entries.lock().unwrap().iter().cloned().collect()
+    }
+}
+
+impl AuditSink for RingBufferSink {
+    fn record(&self, entry: AuditEntry) {
+        let mut entries = self.entries.lock().unwrap();
+        if entries.len() == self.capacity {
+            entries.pop_front();
+        }
+        entries.push_back(entry);
+    }
+}
+
+/// Appends entries as JSON lines to a file, pruning entries older than
+/// `retention_days` on each write.
+pub struct JsonLinesFileSink {
+    path: PathBuf,
+    retention_days: u32,
+}
+
+impl JsonLinesFileSink {
+    /// Create a JSON-lines sink writing to `path`, enforcing `retention_days`.
+    pub fn new(path: impl Into<PathBuf>, retention_days: u32) -> Self {
+        Self {
+            path: path.into(),
+            retention_days,
+        }
+    }
+
+    /// Drop lines whose entries are older than the retention window.
+    fn prune(&self, now: DateTime<Utc>) {
+        let cutoff = now - Duration::days(self.retention_days as i64);
+        let Ok(contents) = std::fs::read_to_string(&self.path) else {
+            return;
+        };
+        let kept: Vec<&str> = contents
+            .lines()
+            .filter(|line| {
+                serde_json::from_str::<AuditEntry>(line)
+                    .map(|e| e.timestamp >= cutoff)
+                    .unwrap_or(true)
+            })
+            .collect();
+        if kept.len() != contents.lines().count() {
+            let mut out = kept.join("\n");
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            let _ = std::fs::write(&self.path, out);
+        }
+    }
+}
+
+impl AuditSink for JsonLinesFileSink {
+    fn record(&self, entry: AuditEntry) {
+        if let Ok(mut file) = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+        {
+            if let Ok(line) = serde_json::to_string(&entry) {
+                let _ = writeln!(file, "{line}");
+            }
+        }
+        self.prune(entry.timestamp);
+    }
+}
+
+/// Formats entries in a syslog-style single line and forwards them to a writer.
+pub struct SyslogSink<W: Write + Send> {
+    writer: Mutex<W>,
+}
+
+impl<W: Write + Send> SyslogSink<W> {
+    /// Create a syslog-style sink writing formatted lines to `writer`.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer: Mutex::new(writer),
+        }
+    }
+
+    fn priority(severity: &AuditSeverity) -> u8 {
+        // RFC 5424 severity codes.
+        match severity {
+            AuditSeverity::Informational => 6,
+            AuditSeverity::Warning => 4,
+            AuditSeverity::Error => 3,
+            AuditSeverity::Critical => 2,
+        }
+    }
+}
+
+impl<W: Write + Send> AuditSink for SyslogSink<W> {
+    fn record(&self, entry: AuditEntry) {
+        let mut writer = self.writer.lock().unwrap();
+        let _ = writeln!(
+            writer,
+            "<{}>{} constellation audit[{:?}]: actor={} resource={} action={} success={}",
+            Self::priority(&entry.severity),
+            entry.timestamp.to_rfc3339(),
+            entry.event_type,
+            entry.actor,
+            entry.resource,
+            entry.action,
+            entry.success,
+        );
+    }
+}
+
+impl McpSecurityContext {
+    /// Dispatch `entry` to `sink` only if the context's `events_to_log`
+    /// configuration admits it, honoring each event's `log_success`/
+    /// `log_failure` flags and severity.
+    pub fn emit_audit(&self, sink: &dyn AuditSink, entry: AuditEntry) {
+        let admitted = self.audit_logging.enabled
+            && self.audit_logging.events_to_log.iter().any(|cfg| {
+                cfg.event_type == entry.event_type
+                    && cfg.severity == entry.severity
+                    && if entry.success {
+                        cfg.log_success
+                    } else {
+                        cfg.log_failure
+                    }
+            });
+        if admitted {
+            sink.record(entry);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::mcp::SecurityLevel;
+
+    #[test]
+    fn test_ring_buffer_evicts_oldest() {
+        let sink = RingBufferSink::new(2);
+        for i in 0..3 {
+            sink.record(AuditEntry::new(
+                AuditEventType::Authentication,
+                AuditSeverity::Informational,
+                format!("actor-{i}"),
+                "resource",
+                "login",
+                true,
+            ));
+        }
+        let entries = sink.entries();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].actor, "actor-1");
+    }
+
+    #[test]
+    fn test_context_filters_unconfigured_events() {
+        let ctx = McpSecurityContext::new(SecurityLevel::High);
+        let sink = RingBufferSink::new(8);
+
+        // Authentication successes are logged in the default config.
+        ctx.emit_audit(
+            &sink,
+            AuditEntry::new(
+                AuditEventType::Authentication,
+                AuditSeverity::Informational,
+                "agent-a",
+                "envelope",
+                "sign",
+                true,
+            ),
+        );
+        // Authorization successes are not (log_success = false by default).
+        ctx.emit_audit(
+            &sink,
+            AuditEntry::new(
+                AuditEventType::Authorization,
+                AuditSeverity::Warning,
+                "agent-a",
+                "envelope",
+                "verify",
+                true,
+            ),
+        );
+
+        assert_eq!(sink.entries().len(), 1);
+    }
+}
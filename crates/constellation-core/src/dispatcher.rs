@@ -0,0 +1,319 @@
+//! Admission control that bounds in-flight tasks and RAM per executor.
+//!
+//! [`ExecutorConfig`] declares `max_concurrent_tasks` and a `memory_mb`
+//! requirement, but nothing enforces them, so a slow executor can accumulate
+//! unbounded queued work and exhaust memory. [`ExecutorDispatcher`] wraps each
+//! executor in a Tokio [`Semaphore`] sized to its concurrency limit, and tracks
+//! a crate-wide RAM budget through a shared semaphore where an admitted task
+//! acquires `memory_mb` permits and releases them on completion.
+//!
+//! When permits are unavailable the dispatcher applies backpressure — callers
+//! [`admit`](ExecutorDispatcher::admit) await a slot rather than buffering more
+//! work, or call [`try_admit`](ExecutorDispatcher::try_admit) for a non-blocking
+//! attempt that surfaces a [`ResourceExhaustion`] signal. This mirrors the
+//! bounded-buffering fix in distributed block managers, where one slow node
+//! otherwise drives excessive RAM use.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::models::hybrid_agent::{ExecutorConfig, HybridAgentConfig};
+
+/// Signal that admission was refused because a resource limit is saturated.
+#[derive(Debug, PartialEq)]
+pub enum ResourceExhaustion {
+    /// The executor is already at `max_concurrent_tasks`.
+    Concurrency {
+        /// The executor that is saturated.
+        executor_id: String,
+    },
+    /// The crate-wide RAM budget cannot satisfy the task's `memory_mb`.
+    Memory {
+        /// MB requested by the task.
+        requested_mb: u32,
+        /// MB currently free in the budget.
+        available_mb: usize,
+    },
+}
+
+impl std::fmt::Display for ResourceExhaustion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ResourceExhaustion::Concurrency { executor_id } => {
+                write!(f, "executor {executor_id} is at its concurrency limit")
+            }
+            ResourceExhaustion::Memory {
+                requested_mb,
+                available_mb,
+            } => write!(
+                f,
+                "RAM budget exhausted: {requested_mb} MB requested, {available_mb} MB available"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ResourceExhaustion {}
+
+/// Holds the concurrency and RAM permits for one admitted task; dropping it
+/// returns both to their semaphores, relieving backpressure.
+pub struct AdmissionGuard {
+    _slot: OwnedSemaphorePermit,
+    _ram: OwnedSemaphorePermit,
+}
+
+/// Bounds in-flight tasks per executor and aggregate RAM across all executors.
+pub struct ExecutorDispatcher {
+    slots: Mutex<HashMap<String, Arc<Semaphore>>>,
+    ram: Arc<Semaphore>,
+    ram_max_mb: usize,
+}
+
+impl ExecutorDispatcher {
+    /// Create a dispatcher with a crate-wide RAM budget of `buffer_ram_max_mb`.
+    pub fn new(buffer_ram_max_mb: usize) -> Self {
+        Self {
+            slots: Mutex::new(HashMap::new()),
+            ram: Arc::new(Semaphore::new(buffer_ram_max_mb)),
+            ram_max_mb: buffer_ram_max_mb,
+        }
+    }
+
+    /// Build a dispatcher whose RAM budget is the hybrid agent's aggregate
+    /// [`total_resource_requirements`](HybridAgentConfig::total_resource_requirements),
+    /// so it never admits more concurrent work than the configured
+    /// [`ResourceAllocation`](crate::models::hybrid_agent::ResourceAllocation)
+    /// policies provide for, and pre-registers each executor's concurrency slot.
+    pub fn from_config(config: &HybridAgentConfig) -> Self {
+        let budget = config.total_resource_requirements().memory_mb as usize;
+        let dispatcher = Self::new(budget);
+        dispatcher.register_slots(config);
+        dispatcher
+    }
+
+    /// Build a dispatcher against an explicit crate-wide RAM budget rather
+    /// than one auto-sized to `config`, refusing construction with
+    /// [`ResourceExhaustion::Memory`] if `config`'s executors would already
+    /// [`would_overcommit`](Self::would_overcommit) that budget at full
+    /// concurrency. Use this when the budget is a fixed cluster-wide ceiling
+    /// shared across multiple configs rather than derived from one of them.
+    pub fn from_config_with_budget(
+        config: &HybridAgentConfig,
+        buffer_ram_max_mb: usize,
+    ) -> Result<Self, ResourceExhaustion> {
+        let dispatcher = Self::new(buffer_ram_max_mb);
+        if dispatcher.would_overcommit(config) {
+            return Err(ResourceExhaustion::Memory {
+                requested_mb: config.total_resource_requirements().memory_mb,
+                available_mb: buffer_ram_max_mb,
+            });
+        }
+        dispatcher.register_slots(config);
+        Ok(dispatcher)
+    }
+
+    /// Pre-register each of `config`'s executors with a concurrency slot.
+    fn register_slots(&self, config: &HybridAgentConfig) {
+        let mut slots = self.slots.lock().unwrap();
+        for executor in &config.executors {
+            slots.insert(
+                executor.id.clone(),
+                Arc::new(Semaphore::new(executor.max_concurrent_tasks as usize)),
+            );
+        }
+    }
+
+    /// The crate-wide RAM budget in MB.
+    pub fn capacity_mb(&self) -> usize {
+        self.ram_max_mb
+    }
+
+    /// MB currently free in the RAM budget.
+    pub fn available_mb(&self) -> usize {
+        self.ram.available_permits()
+    }
+
+    /// Whether admitting every executor at full concurrency would exceed the
+    /// RAM budget — i.e. the dispatcher would over-commit beyond its policy.
+    pub fn would_overcommit(&self, config: &HybridAgentConfig) -> bool {
+        config.total_resource_requirements().memory_mb as usize > self.ram_max_mb
+    }
+
+    /// Grant `extra` additional concurrency permits to `executor_id`'s slot,
+    /// e.g. in response to a [`FallbackAction::ScaleResources`](crate::models::hybrid_agent::FallbackAction::ScaleResources)
+    /// handler. A no-op if the executor has no registered slot yet.
+    pub fn grow_slot(&self, executor_id: &str, extra: u32) {
+        if let Some(slot) = self.slots.lock().unwrap().get(executor_id) {
+            slot.add_permits(extra as usize);
+        }
+    }
+
+    /// Concurrency semaphore for `executor`, created on first use.
+    fn slot(&self, executor: &ExecutorConfig) -> Arc<Semaphore> {
+        self.slots
+            .lock()
+            .unwrap()
+            .entry(executor.id.clone())
+            .or_insert_with(|| Arc::new(Semaphore::new(executor.max_concurrent_tasks as usize)))
+            .clone()
+    }
+
+    /// Attempt to admit a task for `executor` without waiting.
+    ///
+    /// Acquires one concurrency permit and `memory_mb` RAM permits; returns the
+    /// matching [`ResourceExhaustion`] variant if either is unavailable. The
+    /// returned [`AdmissionGuard`] releases both on drop.
+    pub fn try_admit(&self, executor: &ExecutorConfig) -> Result<AdmissionGuard, ResourceExhaustion> {
+        let needed = executor.resource_requirements.memory_mb;
+        if needed as usize > self.ram_max_mb {
+            return Err(ResourceExhaustion::Memory {
+                requested_mb: needed,
+                available_mb: self.ram_max_mb,
+            });
+        }
+
+        let slot = self
+            .slot(executor)
+            .try_acquire_owned()
+            .map_err(|_| ResourceExhaustion::Concurrency {
+                executor_id: executor.id.clone(),
+            })?;
+
+        let ram = self
+            .ram
+            .clone()
+            .try_acquire_many_owned(needed)
+            .map_err(|_| ResourceExhaustion::Memory {
+                requested_mb: needed,
+                available_mb: self.ram.available_permits(),
+            })?;
+
+        Ok(AdmissionGuard {
+            _slot: slot,
+            _ram: ram,
+        })
+    }
+
+    /// Admit a task for `executor`, awaiting a concurrency slot and RAM permits
+    /// when the limits are saturated (backpressure rather than buffering).
+    ///
+    /// A task whose `memory_mb` exceeds the whole RAM budget can never be
+    /// admitted and fails immediately with [`ResourceExhaustion::Memory`].
+    pub async fn admit(&self, executor: &ExecutorConfig) -> Result<AdmissionGuard, ResourceExhaustion> {
+        let needed = executor.resource_requirements.memory_mb;
+        if needed as usize > self.ram_max_mb {
+            return Err(ResourceExhaustion::Memory {
+                requested_mb: needed,
+                available_mb: self.ram_max_mb,
+            });
+        }
+
+        let slot = self
+            .slot(executor)
+            .acquire_owned()
+            .await
+            .map_err(|_| ResourceExhaustion::Concurrency {
+                executor_id: executor.id.clone(),
+            })?;
+
+        let ram = self
+            .ram
+            .clone()
+            .acquire_many_owned(needed)
+            .await
+            .map_err(|_| ResourceExhaustion::Memory {
+                requested_mb: needed,
+                available_mb: self.ram.available_permits(),
+            })?;
+
+        Ok(AdmissionGuard {
+            _slot: slot,
+            _ram: ram,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::hybrid_agent::{ExecutorDomain, HybridAgentConfig};
+
+    fn executor(id: &str, memory_mb: u32, max_concurrent_tasks: u32) -> ExecutorConfig {
+        let mut executor = ExecutorConfig::new(id.to_string(), ExecutorDomain::CodeGeneration);
+        executor.resource_requirements.memory_mb = memory_mb;
+        executor.max_concurrent_tasks = max_concurrent_tasks;
+        executor
+    }
+
+    #[test]
+    fn test_try_admit_succeeds_within_budget() {
+        let dispatcher = ExecutorDispatcher::new(1024);
+        let executor = executor("exec-1", 256, 2);
+        let guard = dispatcher.try_admit(&executor).unwrap();
+        assert_eq!(dispatcher.available_mb(), 1024 - 256);
+        drop(guard);
+        assert_eq!(dispatcher.available_mb(), 1024);
+    }
+
+    #[test]
+    fn test_try_admit_rejects_when_ram_budget_exhausted() {
+        let dispatcher = ExecutorDispatcher::new(128);
+        let executor = executor("exec-1", 256, 2);
+        let err = dispatcher.try_admit(&executor).unwrap_err();
+        assert_eq!(
+            err,
+            ResourceExhaustion::Memory {
+                requested_mb: 256,
+                available_mb: 128,
+            }
+        );
+    }
+
+    #[test]
+    fn test_try_admit_rejects_when_concurrency_slot_saturated() {
+        let dispatcher = ExecutorDispatcher::new(1024);
+        let executor = executor("exec-1", 100, 1);
+        let _first = dispatcher.try_admit(&executor).unwrap();
+        let err = dispatcher.try_admit(&executor).unwrap_err();
+        assert_eq!(
+            err,
+            ResourceExhaustion::Concurrency {
+                executor_id: "exec-1".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_would_overcommit_detects_budget_shortfall() {
+        let mut config = HybridAgentConfig::new("agent".to_string(), "desc".to_string());
+        config.executors.push(executor("exec-1", 2048, 1));
+        config.executors.push(executor("exec-2", 4096, 1));
+
+        let generous = ExecutorDispatcher::new(8192);
+        assert!(!generous.would_overcommit(&config));
+
+        let tight = ExecutorDispatcher::new(1024);
+        assert!(tight.would_overcommit(&config));
+    }
+
+    #[test]
+    fn test_from_config_with_budget_rejects_overcommit() {
+        let mut config = HybridAgentConfig::new("agent".to_string(), "desc".to_string());
+        config.executors.push(executor("exec-1", 4096, 1));
+        let total_needed = config.total_resource_requirements().memory_mb;
+
+        let err = ExecutorDispatcher::from_config_with_budget(&config, 1024).unwrap_err();
+        assert_eq!(
+            err,
+            ResourceExhaustion::Memory {
+                requested_mb: total_needed,
+                available_mb: 1024,
+            }
+        );
+
+        let dispatcher = ExecutorDispatcher::from_config_with_budget(&config, 8192).unwrap();
+        assert_eq!(dispatcher.capacity_mb(), 8192);
+    }
+}
@@ -0,0 +1,229 @@
+//! Threshold (k-of-n) key escrow using Shamir secret sharing over GF(256).
+//!
+//! For `SecurityLevel::Critical` envelopes the symmetric content key can be
+//! split across multiple agents so that no single agent can decrypt alone. Each
+//! secret byte `s` is hidden in a random polynomial `p(x) = s + a1·x + … +
+//! a(k-1)·x^(k-1)` over GF(256); share `i` is the pair `(i, p(i))` for distinct
+//! nonzero `i`. Reconstruction uses Lagrange interpolation at `x = 0` over any
+//! `k` shares.
+
+use rand::Rng;
+
+use crate::models::mcp::McpEncryptedMessage;
+
+/// Error returned by escrow operations.
+#[derive(Debug, PartialEq)]
+pub enum EscrowError {
+    /// `k` was zero or greater than `n`.
+    InvalidThreshold { k: u8, n: u8 },
+    /// Fewer than `k` shares were supplied for reconstruction.
+    InsufficientShares { have: usize, need: usize },
+    /// Two shares carried the same index, or an index was zero.
+    InvalidShareIndex,
+    /// Supplied shares disagreed on length or key id.
+    InconsistentShares,
+}
+
+impl std::fmt::Display for EscrowError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EscrowError::InvalidThreshold { k, n } => {
+                write!(f, "invalid threshold: k={k} n={n} (require 1 <= k <= n)")
+            }
+            EscrowError::InsufficientShares { have, need } => {
+                write!(f, "insufficient shares: have {have}, need {need}")
+            }
+            EscrowError::InvalidShareIndex => write!(f, "share indices must be distinct and nonzero"),
+            EscrowError::InconsistentShares => write!(f, "shares disagree on length or key id"),
+        }
+    }
+}
+
+impl std::error::Error for EscrowError {}
+
+/// A single Shamir share of a split content key.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyShare {
+    /// The `key_id` this share reconstructs.
+    pub key_id: String,
+    /// The x-coordinate (share index), distinct and nonzero across shares.
+    pub index: u8,
+    /// `p(index)` evaluated per secret byte.
+    pub bytes: Vec<u8>,
+}
+
+/// Multiply two elements of GF(256) using carryless multiply reduced mod 0x11b.
+fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0u8;
+    for _ in 0..8 {
+        if b & 1 != 0 {
+            product ^= a;
+        }
+        let hi = a & 0x80;
+        a <<= 1;
+        if hi != 0 {
+            a ^= 0x1b; // reduction polynomial x^8 + x^4 + x^3 + x + 1
+        }
+        b >>= 1;
+    }
+    product
+}
+
+/// Multiplicative inverse in GF(256) via exponentiation (a^254 = a^-1).
+fn gf_inv(a: u8) -> u8 {
+    let mut result = 1u8;
+    let mut base = a;
+    // a^254 = a^(11111110b)
+    for bit in 1..8 {
+        base = gf_mul(base, base);
+        if (254 >> bit) & 1 == 1 {
+            result = gf_mul(result, base);
+        }
+    }
+    result
+}
+
+/// Evaluate polynomial with `coeffs` (ascending degree) at `x` in GF(256).
+fn gf_eval(coeffs: &[u8], x: u8) -> u8 {
+    // Horner's method.
+    let mut acc = 0u8;
+    for &c in coeffs.iter().rev() {
+        acc = gf_mul(acc, x) ^ c;
+    }
+    acc
+}
+
+/// Split an arbitrary secret into `n` shares requiring `k` to reconstruct.
+pub fn split_secret(secret: &[u8], k: u8, n: u8, key_id: &str) -> Result<Vec<KeyShare>, EscrowError> {
+    if k == 0 || k > n {
+        return Err(EscrowError::InvalidThreshold { k, n });
+    }
+
+    let mut rng = rand::thread_rng();
+    let mut shares: Vec<KeyShare> = (1..=n)
+        .map(|index| KeyShare {
+            key_id: key_id.to_string(),
+            index,
+            bytes: Vec::with_capacity(secret.len()),
+        })
+        .collect();
+
+    for &secret_byte in secret {
+        // Build a degree-(k-1) polynomial with the secret as the constant term.
+        let mut coeffs = vec![secret_byte];
+        for _ in 1..k {
+            coeffs.push(rng.r#gen());
+        }
+        for share in shares.iter_mut() {
+            share.bytes.push(gf_eval(&coeffs, share.index));
+        }
+    }
+
+    Ok(shares)
+}
+
+/// Reconstruct a secret from at least `k` shares via Lagrange interpolation at 0.
+pub fn reconstruct_secret(shares: &[KeyShare], k: u8) -> Result<Vec<u8>, EscrowError> {
+    if shares.len() < k as usize {
+        return Err(EscrowError::InsufficientShares {
+            have: shares.len(),
+            need: k as usize,
+        });
+    }
+
+    let selected = &shares[..k as usize];
+    let len = selected[0].bytes.len();
+    let key_id = &selected[0].key_id;
+
+    // Distinct, nonzero indices and consistent shapes are required.
+    let mut seen = std::collections::HashSet::new();
+    for share in selected {
+        if share.index == 0 || !seen.insert(share.index) {
+            return Err(EscrowError::InvalidShareIndex);
+        }
+        if share.bytes.len() != len || &share.key_id != key_id {
+            return Err(EscrowError::InconsistentShares);
+        }
+    }
+
+    let mut secret = Vec::with_capacity(len);
+    for byte_idx in 0..len {
+        let mut value = 0u8;
+        for (i, si) in selected.iter().enumerate() {
+            // Lagrange basis l_i(0) = product_{j!=i} (-x_j)/(x_i - x_j); in GF(256)
+            // subtraction is XOR, so -x_j == x_j.
+            let mut numerator = 1u8;
+            let mut denominator = 1u8;
+            for (j, sj) in selected.iter().enumerate() {
+                if i == j {
+                    continue;
+                }
+                numerator = gf_mul(numerator, sj.index);
+                denominator = gf_mul(denominator, si.index ^ sj.index);
+            }
+            let basis = gf_mul(numerator, gf_inv(denominator));
+            value ^= gf_mul(si.bytes[byte_idx], basis);
+        }
+        secret.push(value);
+    }
+
+    Ok(secret)
+}
+
+impl McpEncryptedMessage {
+    /// Split the symmetric content `key` for this message into `n` escrow shares
+    /// requiring `k` to reconstruct. Each share is tagged with this message's
+    /// `key_id` so a coordinator can route shares to the right envelope.
+    pub fn split_key(&self, key: &[u8], k: u8, n: u8) -> Result<Vec<KeyShare>, EscrowError> {
+        split_secret(key, k, n, &self.key_id)
+    }
+
+    /// Reconstruct the content key from `shares` given threshold `k`.
+    pub fn reconstruct_key(shares: &[KeyShare], k: u8) -> Result<Vec<u8>, EscrowError> {
+        reconstruct_secret(shares, k)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_and_reconstruct_any_k() {
+        let secret = b"super-secret-aes-key-32-bytes!!!";
+        let shares = split_secret(secret, 3, 5, "key-1").unwrap();
+        assert_eq!(shares.len(), 5);
+
+        // Any 3 of the 5 shares reconstruct the secret.
+        let subset = vec![shares[0].clone(), shares[2].clone(), shares[4].clone()];
+        assert_eq!(reconstruct_secret(&subset, 3).unwrap(), secret);
+    }
+
+    #[test]
+    fn test_rejects_invalid_threshold() {
+        assert_eq!(
+            split_secret(b"x", 0, 3, "k"),
+            Err(EscrowError::InvalidThreshold { k: 0, n: 3 })
+        );
+        assert_eq!(
+            split_secret(b"x", 4, 3, "k"),
+            Err(EscrowError::InvalidThreshold { k: 4, n: 3 })
+        );
+    }
+
+    #[test]
+    fn test_insufficient_shares() {
+        let shares = split_secret(b"abc", 3, 5, "k").unwrap();
+        assert_eq!(
+            reconstruct_secret(&shares[..2], 3),
+            Err(EscrowError::InsufficientShares { have: 2, need: 3 })
+        );
+    }
+
+    #[test]
+    fn test_gf_inverse_is_inverse() {
+        for a in 1u8..=255 {
+            assert_eq!(gf_mul(a, gf_inv(a)), 1);
+        }
+    }
+}
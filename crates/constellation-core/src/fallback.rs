@@ -0,0 +1,507 @@
+//! Background runtime for [`FallbackStrategy`](crate::models::hybrid_agent::FallbackStrategy).
+//!
+//! `HybridAgentConfig::fallback_strategies` only declares which
+//! [`FallbackTrigger`] should lead to which [`FallbackAction`]; nothing
+//! evaluates the triggers against live executor behaviour. [`FallbackMonitor`]
+//! is spawned as a background job (mirroring the admission model in
+//! [`dispatcher`](crate::dispatcher)): on every `sync_frequency_ms` tick it
+//! pulls each executor's current [`ExecutorPerformance`] from a supplied
+//! [`MetricsSource`], compares it against the config's [`PerformanceTargets`],
+//! and fires the matching strategies in `priority` order (lowest value first).
+//!
+//! Each [`FallbackAction`] maps to a concrete handler: `SwitchExecutor` picks
+//! another executor serving the same [`ExecutorDomain`]; `ScaleResources`
+//! grows the triggering executor's concurrency slot on the
+//! [`ExecutorDispatcher`]; `RetryWithBackoff` computes an exponential delay
+//! bounded by the strategy's `timeout_ms`; `NotifyHuman` and `AbortTask` emit
+//! events for the caller to act on. Every fired action is reported through a
+//! [`FallbackSink`], and repeated firings of the same (executor, trigger) pair
+//! are debounced for `timeout_ms` so a single sustained condition does not
+//! re-fire on every tick.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+
+use crate::dispatcher::ExecutorDispatcher;
+use crate::models::hybrid_agent::{
+    ExecutorPerformance, FallbackAction, FallbackStrategy, FallbackTrigger, HybridAgentConfig,
+    PerformanceTargets,
+};
+
+/// Supplies the live [`ExecutorPerformance`] the monitor evaluates triggers
+/// against, e.g. a metrics store fed by the dispatcher or telemetry pipeline.
+pub trait MetricsSource: Send + Sync {
+    /// Current performance snapshot for `executor_id`, if it is reporting.
+    fn performance(&self, executor_id: &str) -> Option<ExecutorPerformance>;
+}
+
+/// Receives the outcome of each fired [`FallbackAction`].
+pub trait FallbackSink: Send + Sync {
+    /// Record one fired action.
+    fn emit(&self, event: FallbackEvent);
+}
+
+/// The result of evaluating and acting on one [`FallbackStrategy`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct FallbackEvent {
+    /// Executor the triggering condition was observed on.
+    pub executor_id: String,
+    /// The trigger that fired.
+    pub trigger: FallbackTrigger,
+    /// The action taken in response.
+    pub action: FallbackAction,
+    /// Action-specific detail, e.g. the executor switched to or the computed
+    /// backoff in milliseconds.
+    pub detail: String,
+}
+
+/// Evaluate whether `trigger` fires for `perf` against `targets` /
+/// `strategy_timeout_ms`, and whether the admission layer is saturated for
+/// `executor_id` (used by [`FallbackTrigger::ResourceExhaustion`]).
+fn trigger_fires(
+    trigger: &FallbackTrigger,
+    perf: &ExecutorPerformance,
+    targets: &PerformanceTargets,
+    strategy_timeout_ms: u32,
+    resource_exhausted: bool,
+) -> bool {
+    match trigger {
+        FallbackTrigger::HighLatency => perf.avg_latency_ms > targets.latency_target_ms,
+        FallbackTrigger::LowSuccessRate => (1.0 - perf.error_rate) < targets.success_rate_target,
+        FallbackTrigger::HighErrorRate => perf.error_rate > (1.0 - targets.success_rate_target),
+        FallbackTrigger::ResourceExhaustion => resource_exhausted,
+        FallbackTrigger::BudgetExceeded => {
+            perf.cost_per_1k_tasks > 0.0
+                && perf.throughput_tps / perf.cost_per_1k_tasks < targets.cost_efficiency_target
+        }
+        FallbackTrigger::QualityBelowThreshold => {
+            (1.0 - perf.error_rate) * perf.availability < targets.quality_score_target
+        }
+        FallbackTrigger::AvailabilityBelowThreshold => perf.availability < targets.availability_target,
+        FallbackTrigger::Timeout => perf.p99_latency_ms > strategy_timeout_ms,
+    }
+}
+
+/// Debounce + retry bookkeeping for one (executor, trigger) pair.
+struct FireState {
+    last_fired: Instant,
+    retry_attempts: u32,
+}
+
+/// Evaluates [`FallbackStrategy`]s against live metrics and fires their
+/// actions on a timer.
+pub struct FallbackMonitor {
+    config: HybridAgentConfig,
+    dispatcher: Option<Arc<ExecutorDispatcher>>,
+    metrics: Arc<dyn MetricsSource>,
+    sink: Arc<dyn FallbackSink>,
+    state: std::sync::Mutex<HashMap<(String, FallbackTrigger), FireState>>,
+}
+
+impl FallbackMonitor {
+    /// Build a monitor over `config`'s executors and `fallback_strategies`.
+    /// `dispatcher`, when supplied, backs `ResourceExhaustion` detection and
+    /// `ScaleResources` handling.
+    pub fn new(
+        config: HybridAgentConfig,
+        dispatcher: Option<Arc<ExecutorDispatcher>>,
+        metrics: Arc<dyn MetricsSource>,
+        sink: Arc<dyn FallbackSink>,
+    ) -> Self {
+        Self {
+            config,
+            dispatcher,
+            metrics,
+            sink,
+            state: std::sync::Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Run one evaluation pass over every executor and fallback strategy,
+    /// firing (and debouncing) matching actions. Exposed directly for callers
+    /// that want to drive ticks themselves instead of spawning.
+    pub fn tick(&self) {
+        let strategies = self.sorted_strategies();
+        for executor in &self.config.executors {
+            let Some(perf) = self.metrics.performance(&executor.id) else {
+                continue;
+            };
+            let resource_exhausted = self
+                .dispatcher
+                .as_ref()
+                .map(|d| d.available_mb() == 0)
+                .unwrap_or(false);
+
+            for strategy in &strategies {
+                if !trigger_fires(
+                    &strategy.trigger,
+                    &perf,
+                    &self.config.performance_targets,
+                    strategy.timeout_ms,
+                    resource_exhausted,
+                ) {
+                    continue;
+                }
+                if self.debounced(&executor.id, &strategy.trigger, strategy.timeout_ms) {
+                    continue;
+                }
+                let detail = self.handle(executor.id.as_str(), strategy);
+                self.sink.emit(FallbackEvent {
+                    executor_id: executor.id.clone(),
+                    trigger: strategy.trigger.clone(),
+                    action: strategy.action.clone(),
+                    detail,
+                });
+            }
+        }
+    }
+
+    /// Strategies ordered by ascending `priority` (lower fires first).
+    fn sorted_strategies(&self) -> Vec<FallbackStrategy> {
+        let mut strategies = self.config.fallback_strategies.clone();
+        strategies.sort_by_key(|s| s.priority);
+        strategies
+    }
+
+    /// Whether (`executor_id`, `trigger`) fired within the last `timeout_ms`
+    /// and should be suppressed; records the firing otherwise.
+    fn debounced(&self, executor_id: &str, trigger: &FallbackTrigger, timeout_ms: u32) -> bool {
+        let mut state = self.state.lock().expect("fallback state mutex poisoned");
+        let key = (executor_id.to_string(), trigger.clone());
+        let now = Instant::now();
+        let window = Duration::from_millis(timeout_ms as u64);
+        match state.get_mut(&key) {
+            Some(existing) if now.duration_since(existing.last_fired) < window => true,
+            Some(existing) => {
+                existing.last_fired = now;
+                false
+            }
+            None => {
+                state.insert(
+                    key,
+                    FireState {
+                        last_fired: now,
+                        retry_attempts: 0,
+                    },
+                );
+                false
+            }
+        }
+    }
+
+    /// Execute `strategy.action` for `executor_id`, returning a human-readable
+    /// detail string for the emitted [`FallbackEvent`].
+    fn handle(&self, executor_id: &str, strategy: &FallbackStrategy) -> String {
+        match &strategy.action {
+            FallbackAction::SwitchExecutor => self.switch_executor(executor_id),
+            FallbackAction::ScaleResources => self.scale_resources(executor_id),
+            FallbackAction::RetryWithBackoff => {
+                self.retry_with_backoff(executor_id, &strategy.trigger, strategy.timeout_ms)
+            }
+            FallbackAction::NotifyHuman => {
+                format!("human notified of {:?} on executor {executor_id}", strategy.trigger)
+            }
+            FallbackAction::AbortTask => format!("task on executor {executor_id} aborted"),
+            FallbackAction::ReduceQuality => {
+                format!("executor {executor_id} instructed to reduce output quality")
+            }
+            FallbackAction::IncreaseBudget => {
+                format!("budget increase requested for executor {executor_id}")
+            }
+            FallbackAction::UseAlternativeStrategy => {
+                format!("coordination fell back to an alternative strategy for executor {executor_id}")
+            }
+        }
+    }
+
+    /// Pick another executor serving the same domain as `executor_id`.
+    fn switch_executor(&self, executor_id: &str) -> String {
+        let Some(failing) = self.config.executors.iter().find(|e| e.id == executor_id) else {
+            return format!("no replacement found for unknown executor {executor_id}");
+        };
+        match self
+            .config
+            .executors
+            .iter()
+            .find(|e| e.id != executor_id && e.domain == failing.domain)
+        {
+            Some(replacement) => format!("switched from {executor_id} to {}", replacement.id),
+            None => format!("no alternate executor available in domain {:?}", failing.domain),
+        }
+    }
+
+    /// Grow the executor's concurrency allowance on the admission layer.
+    fn scale_resources(&self, executor_id: &str) -> String {
+        let Some(executor) = self.config.executors.iter().find(|e| e.id == executor_id) else {
+            return format!("cannot scale unknown executor {executor_id}");
+        };
+        match &self.dispatcher {
+            Some(dispatcher) => {
+                let extra = executor.max_concurrent_tasks.max(1);
+                dispatcher.grow_slot(executor_id, extra);
+                format!("granted {extra} extra concurrency permits to {executor_id}")
+            }
+            None => format!("no admission layer attached; cannot scale {executor_id}"),
+        }
+    }
+
+    /// Compute the next exponential backoff for `(executor_id, trigger)`,
+    /// capped by `timeout_ms`.
+    fn retry_with_backoff(
+        &self,
+        executor_id: &str,
+        trigger: &FallbackTrigger,
+        timeout_ms: u32,
+    ) -> String {
+        let mut state = self.state.lock().expect("fallback state mutex poisoned");
+        let key = (executor_id.to_string(), trigger.clone());
+        let attempts = state
+            .get_mut(&key)
+            .map(|s| {
+                s.retry_attempts += 1;
+                s.retry_attempts
+            })
+            .unwrap_or(1);
+        let backoff_ms = (100u64.saturating_mul(1u64 << attempts.min(16))).min(timeout_ms as u64);
+        format!("retry #{attempts} for {executor_id} scheduled after {backoff_ms}ms backoff")
+    }
+}
+
+/// A cancellable handle to a [`FallbackMonitor`] running as a background task.
+pub struct MonitorHandle {
+    join: JoinHandle<()>,
+    cancel: Arc<Notify>,
+}
+
+impl MonitorHandle {
+    /// Stop the monitor loop and await its shutdown.
+    pub async fn cancel(self) {
+        self.cancel.notify_one();
+        let _ = self.join.await;
+    }
+
+    /// Await the monitor loop to completion (it only returns after `cancel`).
+    pub async fn join(self) -> Result<(), tokio::task::JoinError> {
+        self.join.await
+    }
+}
+
+/// Spawn `monitor` as a background job that calls [`FallbackMonitor::tick`]
+/// every `sync_frequency_ms` (from its `CoordinationStrategy`) until cancelled.
+pub fn spawn(monitor: Arc<FallbackMonitor>) -> MonitorHandle {
+    let cancel = Arc::new(Notify::new());
+    let cancel_wait = cancel.clone();
+    let period = Duration::from_millis(monitor.config.coordination.sync_frequency_ms.max(1) as u64);
+
+    let join = tokio::spawn(async move {
+        let mut interval = tokio::time::interval(period);
+        loop {
+            tokio::select! {
+                _ = interval.tick() => monitor.tick(),
+                _ = cancel_wait.notified() => break,
+            }
+        }
+    });
+
+    MonitorHandle { join, cancel }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::hybrid_agent::{ExecutorConfig, ExecutorDomain};
+
+    struct FixedMetrics(HashMap<String, ExecutorPerformance>);
+
+    impl MetricsSource for FixedMetrics {
+        fn performance(&self, executor_id: &str) -> Option<ExecutorPerformance> {
+            self.0.get(executor_id).cloned()
+        }
+    }
+
+    #[derive(Default)]
+    struct RecordingSink(std::sync::Mutex<Vec<FallbackEvent>>);
+
+    impl FallbackSink for RecordingSink {
+        fn emit(&self, event: FallbackEvent) {
+            self.0.lock().unwrap().push(event);
+        }
+    }
+
+    fn targets() -> PerformanceTargets {
+        PerformanceTargets::default()
+    }
+
+    fn executor(id: &str, domain: ExecutorDomain) -> ExecutorConfig {
+        ExecutorConfig::new(id.to_string(), domain)
+    }
+
+    #[test]
+    fn test_trigger_fires_high_latency_when_over_target() {
+        let mut perf = ExecutorPerformance::default();
+        let targets = targets();
+        perf.avg_latency_ms = targets.latency_target_ms + 1;
+        assert!(trigger_fires(
+            &FallbackTrigger::HighLatency,
+            &perf,
+            &targets,
+            1000,
+            false
+        ));
+
+        perf.avg_latency_ms = targets.latency_target_ms;
+        assert!(!trigger_fires(
+            &FallbackTrigger::HighLatency,
+            &perf,
+            &targets,
+            1000,
+            false
+        ));
+    }
+
+    #[test]
+    fn test_trigger_fires_resource_exhaustion_only_when_flagged() {
+        let perf = ExecutorPerformance::default();
+        let targets = targets();
+        assert!(trigger_fires(
+            &FallbackTrigger::ResourceExhaustion,
+            &perf,
+            &targets,
+            1000,
+            true
+        ));
+        assert!(!trigger_fires(
+            &FallbackTrigger::ResourceExhaustion,
+            &perf,
+            &targets,
+            1000,
+            false
+        ));
+    }
+
+    #[test]
+    fn test_tick_fires_matching_strategy_and_emits_one_event() {
+        let mut config = HybridAgentConfig::new("agent".to_string(), "desc".to_string());
+        config
+            .executors
+            .push(executor("exec-1", ExecutorDomain::CodeGeneration));
+        config.fallback_strategies.push(FallbackStrategy {
+            trigger: FallbackTrigger::HighLatency,
+            action: FallbackAction::NotifyHuman,
+            priority: 1,
+            timeout_ms: 60_000,
+        });
+
+        let mut perf = ExecutorPerformance::default();
+        perf.avg_latency_ms = config.performance_targets.latency_target_ms + 1;
+        let metrics: HashMap<String, ExecutorPerformance> =
+            [("exec-1".to_string(), perf)].into_iter().collect();
+
+        let sink = Arc::new(RecordingSink::default());
+        let monitor = FallbackMonitor::new(
+            config,
+            None,
+            Arc::new(FixedMetrics(metrics)),
+            sink.clone(),
+        );
+
+        monitor.tick();
+        let events = sink.0.lock().unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].trigger, FallbackTrigger::HighLatency);
+        assert_eq!(events[0].action, FallbackAction::NotifyHuman);
+    }
+
+    #[test]
+    fn test_tick_debounces_repeated_firing_within_timeout() {
+        let mut config = HybridAgentConfig::new("agent".to_string(), "desc".to_string());
+        config
+            .executors
+            .push(executor("exec-1", ExecutorDomain::CodeGeneration));
+        config.fallback_strategies.push(FallbackStrategy {
+            trigger: FallbackTrigger::HighLatency,
+            action: FallbackAction::NotifyHuman,
+            priority: 1,
+            timeout_ms: 60_000,
+        });
+
+        let mut perf = ExecutorPerformance::default();
+        perf.avg_latency_ms = config.performance_targets.latency_target_ms + 1;
+        let metrics: HashMap<String, ExecutorPerformance> =
+            [("exec-1".to_string(), perf)].into_iter().collect();
+
+        let sink = Arc::new(RecordingSink::default());
+        let monitor = FallbackMonitor::new(
+            config,
+            None,
+            Arc::new(FixedMetrics(metrics)),
+            sink.clone(),
+        );
+
+        monitor.tick();
+        monitor.tick();
+        assert_eq!(sink.0.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_switch_executor_picks_another_executor_in_same_domain() {
+        let mut config = HybridAgentConfig::new("agent".to_string(), "desc".to_string());
+        config
+            .executors
+            .push(executor("exec-1", ExecutorDomain::CodeGeneration));
+        config
+            .executors
+            .push(executor("exec-2", ExecutorDomain::CodeGeneration));
+        config
+            .executors
+            .push(executor("exec-3", ExecutorDomain::DataAnalysis));
+
+        let monitor = FallbackMonitor::new(
+            config,
+            None,
+            Arc::new(FixedMetrics(HashMap::new())),
+            Arc::new(RecordingSink::default()),
+        );
+
+        assert_eq!(monitor.switch_executor("exec-1"), "switched from exec-1 to exec-2");
+        assert_eq!(
+            monitor.switch_executor("exec-3"),
+            "no alternate executor available in domain DataAnalysis"
+        );
+    }
+
+    #[test]
+    fn test_retry_with_backoff_grows_with_each_attempt() {
+        let config = HybridAgentConfig::new("agent".to_string(), "desc".to_string());
+        let monitor = FallbackMonitor::new(
+            config,
+            None,
+            Arc::new(FixedMetrics(HashMap::new())),
+            Arc::new(RecordingSink::default()),
+        );
+
+        let first = monitor.retry_with_backoff("exec-1", &FallbackTrigger::Timeout, 10_000);
+        let second = monitor.retry_with_backoff("exec-1", &FallbackTrigger::Timeout, 10_000);
+        assert_eq!(first, "retry #1 for exec-1 scheduled after 200ms backoff");
+        assert_eq!(second, "retry #2 for exec-1 scheduled after 400ms backoff");
+    }
+
+    #[test]
+    fn test_retry_with_backoff_is_capped_by_timeout_ms() {
+        let config = HybridAgentConfig::new("agent".to_string(), "desc".to_string());
+        let monitor = FallbackMonitor::new(
+            config,
+            None,
+            Arc::new(FixedMetrics(HashMap::new())),
+            Arc::new(RecordingSink::default()),
+        );
+
+        let backoff = monitor.retry_with_backoff("exec-1", &FallbackTrigger::Timeout, 100);
+        assert_eq!(backoff, "retry #1 for exec-1 scheduled after 100ms backoff");
+    }
+}
@@ -0,0 +1,660 @@
+//! Native gRPC serving of AgentCards via prost/tonic.
+//!
+//! [`ProtocolBinding::Grpc`](crate::models::agent::ProtocolBinding::Grpc) can be
+//! *declared* in `supported_interfaces`, but the core types are serde/JSON only.
+//! This module adds the prost-generated wire schema (see `proto/agent.proto`),
+//! `From`/`TryFrom` conversions between the Rust structs and the generated
+//! messages, and a tonic service so a declared gRPC binding is genuinely
+//! reachable rather than merely advertised. `invoke_skill` enforces the
+//! card's [`Agent::authorize`] check (bearer token via the `authorization`
+//! gRPC metadata entry or the request's `bearer_token` field) whenever the
+//! agent declares a [`JwtAuthConfig`](crate::models::authz::JwtAuthConfig).
+
+use std::collections::HashMap;
+
+use tonic::{Request, Response, Status};
+
+use crate::models::agent::{
+    Agent, AgentCapabilities, AgentContact, AgentInterface, AgentProvider, AgentSkill,
+    ProtocolBinding,
+};
+use crate::models::authz::JwtAuthConfig;
+use crate::models::remote_attestation::AttestationEvidence;
+use crate::models::security::{ApiKeyLocation, OAuth2Flows, SecurityScheme};
+
+/// Prost-generated message types for the `constellation.agent.v1` package.
+pub mod pb {
+    tonic::include_proto!("constellation.agent.v1");
+}
+
+/// Error raised when a protobuf message cannot be converted into a core type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProtoConversionError(pub String);
+
+impl std::fmt::Display for ProtoConversionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "proto conversion error: {}", self.0)
+    }
+}
+
+impl std::error::Error for ProtoConversionError {}
+
+fn binding_to_wire(binding: &ProtocolBinding) -> String {
+    match binding {
+        ProtocolBinding::JsonRpc => "JSONRPC",
+        ProtocolBinding::Grpc => "GRPC",
+        ProtocolBinding::HttpJson => "HTTP+JSON",
+    }
+    .to_string()
+}
+
+fn binding_from_wire(wire: &str) -> Result<ProtocolBinding, ProtoConversionError> {
+    match wire {
+        "JSONRPC" => Ok(ProtocolBinding::JsonRpc),
+        "GRPC" => Ok(ProtocolBinding::Grpc),
+        "HTTP+JSON" => Ok(ProtocolBinding::HttpJson),
+        other => Err(ProtoConversionError(format!("unknown protocol binding {other}"))),
+    }
+}
+
+impl From<&AgentSkill> for pb::AgentSkill {
+    fn from(s: &AgentSkill) -> Self {
+        // The wire schema carries only the resolved default text; localized
+        // variants are a Rust-side-only extension for now.
+        pb::AgentSkill {
+            id: s.id.clone(),
+            name: s.name.resolve(None).to_string(),
+            description: s.description.resolve(None).to_string(),
+            tags: s.tags.clone(),
+            examples: s
+                .examples
+                .as_ref()
+                .map(|e| e.resolve(None).to_vec())
+                .unwrap_or_default(),
+            input_modes: s.input_modes.clone().unwrap_or_default(),
+            output_modes: s.output_modes.clone().unwrap_or_default(),
+        }
+    }
+}
+
+impl From<pb::AgentSkill> for AgentSkill {
+    fn from(s: pb::AgentSkill) -> Self {
+        // Empty repeated fields map back to `None` to preserve round-trip equality
+        // with cards that omitted the optional lists entirely.
+        let opt = |v: Vec<String>| if v.is_empty() { None } else { Some(v) };
+        AgentSkill {
+            id: s.id,
+            name: s.name.into(),
+            description: s.description.into(),
+            tags: s.tags,
+            examples: opt(s.examples).map(Into::into),
+            input_modes: opt(s.input_modes),
+            output_modes: opt(s.output_modes),
+            security: None,
+        }
+    }
+}
+
+impl From<&AgentInterface> for pb::AgentInterface {
+    fn from(i: &AgentInterface) -> Self {
+        pb::AgentInterface {
+            url: i.url.clone(),
+            protocol_binding: binding_to_wire(&i.protocol_binding),
+            tenant: i.tenant.clone(),
+        }
+    }
+}
+
+impl TryFrom<pb::AgentInterface> for AgentInterface {
+    type Error = ProtoConversionError;
+
+    fn try_from(i: pb::AgentInterface) -> Result<Self, Self::Error> {
+        Ok(AgentInterface {
+            url: i.url,
+            protocol_binding: binding_from_wire(&i.protocol_binding)?,
+            tenant: i.tenant,
+        })
+    }
+}
+
+impl From<&AgentCapabilities> for pb::AgentCapabilities {
+    fn from(c: &AgentCapabilities) -> Self {
+        pb::AgentCapabilities {
+            streaming: c.streaming,
+            push_notifications: c.push_notifications,
+            state_transition_history: c.state_transition_history,
+        }
+    }
+}
+
+impl From<pb::AgentCapabilities> for AgentCapabilities {
+    fn from(c: pb::AgentCapabilities) -> Self {
+        AgentCapabilities {
+            streaming: c.streaming,
+            push_notifications: c.push_notifications,
+            state_transition_history: c.state_transition_history,
+        }
+    }
+}
+
+fn api_key_location_to_wire(location: &ApiKeyLocation) -> String {
+    match location {
+        ApiKeyLocation::Header => "header",
+        ApiKeyLocation::Query => "query",
+        ApiKeyLocation::Cookie => "cookie",
+    }
+    .to_string()
+}
+
+fn api_key_location_from_wire(wire: &str) -> Result<ApiKeyLocation, ProtoConversionError> {
+    match wire {
+        "header" => Ok(ApiKeyLocation::Header),
+        "query" => Ok(ApiKeyLocation::Query),
+        "cookie" => Ok(ApiKeyLocation::Cookie),
+        other => Err(ProtoConversionError(format!("unknown api key location {other}"))),
+    }
+}
+
+impl From<&OAuth2Flows> for pb::OAuth2Flows {
+    fn from(f: &OAuth2Flows) -> Self {
+        pb::OAuth2Flows {
+            authorization_url: f.authorization_url.clone(),
+            token_url: f.token_url.clone(),
+            refresh_url: f.refresh_url.clone(),
+            scopes: f.scopes.clone(),
+        }
+    }
+}
+
+impl From<pb::OAuth2Flows> for OAuth2Flows {
+    fn from(f: pb::OAuth2Flows) -> Self {
+        OAuth2Flows {
+            authorization_url: f.authorization_url,
+            token_url: f.token_url,
+            refresh_url: f.refresh_url,
+            scopes: f.scopes,
+        }
+    }
+}
+
+impl From<&SecurityScheme> for pb::SecurityScheme {
+    fn from(scheme: &SecurityScheme) -> Self {
+        let mut wire = pb::SecurityScheme {
+            r#type: String::new(),
+            name: None,
+            location: None,
+            scheme: None,
+            bearer_format: None,
+            oauth2_flows: None,
+            open_id_connect_url: None,
+            attestation_endpoint: None,
+            expected_measurement: None,
+        };
+        match scheme {
+            SecurityScheme::ApiKey { name, location } => {
+                wire.r#type = "api_key".to_string();
+                wire.name = Some(name.clone());
+                wire.location = Some(api_key_location_to_wire(location));
+            }
+            SecurityScheme::Http { scheme: http_scheme, bearer_format } => {
+                wire.r#type = "http".to_string();
+                wire.scheme = Some(http_scheme.clone());
+                wire.bearer_format = bearer_format.clone();
+            }
+            SecurityScheme::Oauth2 { flows } => {
+                wire.r#type = "oauth2".to_string();
+                wire.oauth2_flows = Some(pb::OAuth2Flows::from(flows));
+            }
+            SecurityScheme::OpenIdConnect { open_id_connect_url } => {
+                wire.r#type = "open_id_connect".to_string();
+                wire.open_id_connect_url = Some(open_id_connect_url.clone());
+            }
+            SecurityScheme::MutualTls => {
+                wire.r#type = "mutual_tls".to_string();
+            }
+            SecurityScheme::Attestation {
+                attestation_endpoint,
+                expected_measurement,
+            } => {
+                wire.r#type = "attestation".to_string();
+                wire.attestation_endpoint = Some(attestation_endpoint.clone());
+                wire.expected_measurement = Some(expected_measurement.clone());
+            }
+        }
+        wire
+    }
+}
+
+impl TryFrom<pb::SecurityScheme> for SecurityScheme {
+    type Error = ProtoConversionError;
+
+    fn try_from(wire: pb::SecurityScheme) -> Result<Self, Self::Error> {
+        let scheme_type = wire.r#type.clone();
+        match scheme_type.as_str() {
+            "api_key" => Ok(SecurityScheme::ApiKey {
+                name: wire
+                    .name
+                    .ok_or_else(|| ProtoConversionError("api_key scheme missing name".into()))?,
+                location: api_key_location_from_wire(&wire.location.ok_or_else(|| {
+                    ProtoConversionError("api_key scheme missing location".into())
+                })?)?,
+            }),
+            "http" => Ok(SecurityScheme::Http {
+                scheme: wire
+                    .scheme
+                    .ok_or_else(|| ProtoConversionError("http scheme missing scheme".into()))?,
+                bearer_format: wire.bearer_format,
+            }),
+            "oauth2" => Ok(SecurityScheme::Oauth2 {
+                flows: wire
+                    .oauth2_flows
+                    .ok_or_else(|| ProtoConversionError("oauth2 scheme missing flows".into()))?
+                    .into(),
+            }),
+            "open_id_connect" => Ok(SecurityScheme::OpenIdConnect {
+                open_id_connect_url: wire.open_id_connect_url.ok_or_else(|| {
+                    ProtoConversionError("open_id_connect scheme missing url".into())
+                })?,
+            }),
+            "mutual_tls" => Ok(SecurityScheme::MutualTls),
+            "attestation" => Ok(SecurityScheme::Attestation {
+                attestation_endpoint: wire.attestation_endpoint.ok_or_else(|| {
+                    ProtoConversionError("attestation scheme missing endpoint".into())
+                })?,
+                expected_measurement: wire.expected_measurement.ok_or_else(|| {
+                    ProtoConversionError("attestation scheme missing expected_measurement".into())
+                })?,
+            }),
+            other => Err(ProtoConversionError(format!("unknown security scheme type {other}"))),
+        }
+    }
+}
+
+fn security_requirement_to_wire(req: &HashMap<String, Vec<String>>) -> pb::SecurityRequirement {
+    pb::SecurityRequirement {
+        schemes: req
+            .iter()
+            .map(|(name, scopes)| {
+                (
+                    name.clone(),
+                    pb::StringList {
+                        values: scopes.clone(),
+                    },
+                )
+            })
+            .collect(),
+    }
+}
+
+fn security_requirement_from_wire(req: pb::SecurityRequirement) -> HashMap<String, Vec<String>> {
+    req.schemes.into_iter().map(|(name, list)| (name, list.values)).collect()
+}
+
+impl From<&AttestationEvidence> for pb::AttestationEvidence {
+    fn from(e: &AttestationEvidence) -> Self {
+        pb::AttestationEvidence {
+            report: e.report.clone(),
+            measurement: e.measurement.clone(),
+            runtime_data: e.runtime_data.clone(),
+            init_time_data: e.init_time_data.clone(),
+        }
+    }
+}
+
+impl From<pb::AttestationEvidence> for AttestationEvidence {
+    fn from(e: pb::AttestationEvidence) -> Self {
+        AttestationEvidence {
+            report: e.report,
+            measurement: e.measurement,
+            runtime_data: e.runtime_data,
+            init_time_data: e.init_time_data,
+        }
+    }
+}
+
+/// JSON-encode each claim value so arbitrary `serde_json::Value` claims
+/// survive the wire, which only carries string map values.
+fn encode_claim_map(claims: &HashMap<String, serde_json::Value>) -> HashMap<String, String> {
+    claims
+        .iter()
+        .map(|(k, v)| {
+            (
+                k.clone(),
+                serde_json::to_string(v).expect("serde_json::Value always serializes"),
+            )
+        })
+        .collect()
+}
+
+fn decode_claim_map(
+    claims: HashMap<String, String>,
+) -> Result<HashMap<String, serde_json::Value>, ProtoConversionError> {
+    claims
+        .into_iter()
+        .map(|(k, v)| {
+            serde_json::from_str(&v)
+                .map(|value| (k, value))
+                .map_err(|e| ProtoConversionError(format!("invalid claim JSON for {k}: {e}")))
+        })
+        .collect()
+}
+
+impl From<&JwtAuthConfig> for pb::JwtAuthConfig {
+    fn from(config: &JwtAuthConfig) -> Self {
+        pb::JwtAuthConfig {
+            jwks_uri: config.jwks_uri.clone(),
+            userinfo_endpoint: config.userinfo_endpoint.clone(),
+            required_claims: encode_claim_map(&config.required_claims),
+            skill_claims: config
+                .skill_claims
+                .iter()
+                .map(|(skill_id, claims)| {
+                    (
+                        skill_id.clone(),
+                        pb::ClaimMap {
+                            claims: encode_claim_map(claims),
+                        },
+                    )
+                })
+                .collect(),
+            policy_ref: config.policy_ref.clone(),
+        }
+    }
+}
+
+impl TryFrom<pb::JwtAuthConfig> for JwtAuthConfig {
+    type Error = ProtoConversionError;
+
+    fn try_from(config: pb::JwtAuthConfig) -> Result<Self, Self::Error> {
+        Ok(JwtAuthConfig {
+            jwks_uri: config.jwks_uri,
+            userinfo_endpoint: config.userinfo_endpoint,
+            required_claims: decode_claim_map(config.required_claims)?,
+            skill_claims: config
+                .skill_claims
+                .into_iter()
+                .map(|(skill_id, claim_map)| {
+                    decode_claim_map(claim_map.claims).map(|claims| (skill_id, claims))
+                })
+                .collect::<Result<HashMap<_, _>, _>>()?,
+            policy_ref: config.policy_ref,
+        })
+    }
+}
+
+impl From<&AgentProvider> for pb::AgentProvider {
+    fn from(p: &AgentProvider) -> Self {
+        pb::AgentProvider {
+            name: p.name.clone(),
+            url: p.url.clone(),
+            contact_email: p.contact.as_ref().and_then(|c| c.email.clone()),
+        }
+    }
+}
+
+impl From<pb::AgentProvider> for AgentProvider {
+    fn from(p: pb::AgentProvider) -> Self {
+        AgentProvider {
+            name: p.name,
+            url: p.url,
+            contact: p.contact_email.map(|email| AgentContact { email: Some(email) }),
+        }
+    }
+}
+
+impl From<&Agent> for pb::Agent {
+    fn from(a: &Agent) -> Self {
+        pb::Agent {
+            id: a.id.clone(),
+            name: a.name.clone(),
+            description: a.description.resolve(None).to_string(),
+            protocol_version: a.protocol_version.clone(),
+            version: a.version.clone(),
+            default_input_modes: a.default_input_modes.clone(),
+            default_output_modes: a.default_output_modes.clone(),
+            provider: Some((&a.provider).into()),
+            capabilities: Some((&a.capabilities).into()),
+            skills: a.skills.iter().map(pb::AgentSkill::from).collect(),
+            supported_interfaces: a
+                .supported_interfaces
+                .iter()
+                .map(pb::AgentInterface::from)
+                .collect(),
+            // Constellation metadata is carried structurally when present.
+            metadata: a
+                .metadata
+                .as_ref()
+                .and_then(|m| m.get("constellation").cloned())
+                .and_then(|c| serde_json::from_value::<ConstellationMetadataShadow>(c).ok())
+                .map(|m| m.into()),
+            supports_extended_agent_card: a.supports_extended_agent_card,
+            documentation_url: a.documentation_url.clone(),
+            icon_url: a.icon_url.clone(),
+            security_schemes: a
+                .security_schemes
+                .iter()
+                .map(|(name, scheme)| (name.clone(), pb::SecurityScheme::from(scheme)))
+                .collect(),
+            security: a.security.iter().map(security_requirement_to_wire).collect(),
+            attestation: a.attestation.as_ref().map(pb::AttestationEvidence::from),
+            authorization: a.authorization.as_ref().map(pb::JwtAuthConfig::from),
+        }
+    }
+}
+
+/// Serde shadow of `ConstellationMetadata` used purely for proto conversion.
+#[derive(serde::Deserialize)]
+struct ConstellationMetadataShadow {
+    role: String,
+    internal_status: String,
+    #[serde(default)]
+    capabilities: Vec<String>,
+    #[serde(default)]
+    last_seen: Option<String>,
+}
+
+impl From<ConstellationMetadataShadow> for pb::ConstellationMetadata {
+    fn from(m: ConstellationMetadataShadow) -> Self {
+        pb::ConstellationMetadata {
+            role: m.role,
+            internal_status: m.internal_status,
+            capabilities: m.capabilities,
+            last_seen: m.last_seen,
+        }
+    }
+}
+
+impl TryFrom<pb::Agent> for Agent {
+    type Error = ProtoConversionError;
+
+    fn try_from(a: pb::Agent) -> Result<Self, Self::Error> {
+        let provider = a
+            .provider
+            .ok_or_else(|| ProtoConversionError("missing provider".into()))?;
+        let capabilities = a.capabilities.unwrap_or_default();
+        let supported_interfaces = a
+            .supported_interfaces
+            .into_iter()
+            .map(AgentInterface::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        let security_schemes = a
+            .security_schemes
+            .into_iter()
+            .map(|(name, scheme)| SecurityScheme::try_from(scheme).map(|scheme| (name, scheme)))
+            .collect::<Result<HashMap<_, _>, _>>()?;
+        let security = a.security.into_iter().map(security_requirement_from_wire).collect();
+        let attestation = a.attestation.map(AttestationEvidence::from);
+        let authorization = a.authorization.map(JwtAuthConfig::try_from).transpose()?;
+
+        let metadata = a.metadata.map(|m| {
+            serde_json::json!({
+                "constellation": {
+                    "role": m.role,
+                    "internal_status": m.internal_status,
+                    "capabilities": m.capabilities,
+                    "last_seen": m.last_seen,
+                }
+            })
+        });
+
+        Ok(Agent {
+            id: a.id,
+            name: a.name,
+            description: a.description.into(),
+            protocol_version: a.protocol_version,
+            version: a.version,
+            default_input_modes: a.default_input_modes,
+            default_output_modes: a.default_output_modes,
+            provider: provider.into(),
+            capabilities: capabilities.into(),
+            skills: a.skills.into_iter().map(AgentSkill::from).collect(),
+            supported_interfaces,
+            metadata,
+            supports_extended_agent_card: a.supports_extended_agent_card,
+            documentation_url: a.documentation_url,
+            icon_url: a.icon_url,
+            security_schemes,
+            security,
+            supported_protocol_range: crate::models::agent::default_protocol_range(),
+            attestation,
+            authorization,
+            schema_version: crate::models::migration::agent_schema_version(),
+        })
+    }
+}
+
+/// A tonic service that serves a single agent's card and routes skill calls.
+pub struct AgentCardServer {
+    agent: Agent,
+}
+
+impl AgentCardServer {
+    /// Create a server that serves `agent`'s card.
+    pub fn new(agent: Agent) -> Self {
+        Self { agent }
+    }
+}
+
+#[tonic::async_trait]
+impl pb::agent_card_service_server::AgentCardService for AgentCardServer {
+    async fn get_agent_card(
+        &self,
+        _request: Request<pb::GetAgentCardRequest>,
+    ) -> Result<Response<pb::GetAgentCardResponse>, Status> {
+        Ok(Response::new(pb::GetAgentCardResponse {
+            agent: Some((&self.agent).into()),
+        }))
+    }
+
+    async fn invoke_skill(
+        &self,
+        request: Request<pb::InvokeSkillRequest>,
+    ) -> Result<Response<pb::InvokeSkillResponse>, Status> {
+        let token = bearer_token(&request)?;
+        let req = request.into_inner();
+        if !self.agent.has_skill(&req.skill_id) {
+            return Err(Status::not_found(format!("unknown skill {}", req.skill_id)));
+        }
+
+        if self.agent.authorization.is_some() {
+            let Some(token) = token.or(req.bearer_token.clone()) else {
+                return Err(Status::unauthenticated("missing bearer token"));
+            };
+            let decision = self.agent.authorize(&token, &req.skill_id).await;
+            if !decision.allowed {
+                return Err(Status::permission_denied(decision.reason));
+            }
+        }
+
+        // Routing to the concrete executor is the caller's responsibility; the
+        // service confirms reachability and authorization, then echoes the
+        // input as a stub result.
+        Ok(Response::new(pb::InvokeSkillResponse { output: req.input }))
+    }
+}
+
+/// Pull a bearer token out of the standard `authorization` gRPC metadata
+/// entry, if present.
+fn bearer_token<T>(request: &Request<T>) -> Result<Option<String>, Status> {
+    let Some(value) = request.metadata().get("authorization") else {
+        return Ok(None);
+    };
+    let value = value
+        .to_str()
+        .map_err(|_| Status::invalid_argument("authorization metadata is not valid UTF-8"))?;
+    Ok(Some(
+        value.strip_prefix("Bearer ").unwrap_or(value).to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_agent_proto_roundtrip() {
+        let skill = AgentSkill {
+            id: "test-skill".to_string(),
+            name: "Test Skill".to_string().into(),
+            description: "A test skill".to_string().into(),
+            tags: vec!["test".to_string()],
+            examples: Some(vec!["Test example".to_string()].into()),
+            input_modes: Some(vec!["text/plain".to_string()]),
+            output_modes: Some(vec!["application/json".to_string()]),
+            security: None,
+        };
+        let interface = AgentInterface {
+            url: "https://test.com/grpc".to_string(),
+            protocol_binding: ProtocolBinding::Grpc,
+            tenant: Some("test-tenant".to_string()),
+        };
+        let mut agent = Agent::new(
+            "test-agent".to_string(),
+            "Test Agent".to_string(),
+            "A test agent".to_string(),
+            "Test Provider".to_string(),
+            vec![skill],
+            vec![interface],
+        );
+
+        agent.add_security_scheme(
+            "api".to_string(),
+            SecurityScheme::ApiKey {
+                name: "X-Api-Key".to_string(),
+                location: ApiKeyLocation::Header,
+            },
+        );
+        agent.add_security_scheme(
+            "oauth".to_string(),
+            SecurityScheme::Oauth2 {
+                flows: OAuth2Flows {
+                    authorization_url: Some("https://auth.example/authorize".to_string()),
+                    token_url: Some("https://auth.example/token".to_string()),
+                    refresh_url: None,
+                    scopes: HashMap::from([("read".to_string(), "Read access".to_string())]),
+                },
+            },
+        );
+        agent.security = vec![HashMap::from([("api".to_string(), vec!["write".to_string()])])];
+        agent.attestation = Some(AttestationEvidence {
+            report: "cmVwb3J0".to_string(),
+            measurement: "abc123".to_string(),
+            runtime_data: Some(vec![1, 2, 3]),
+            init_time_data: None,
+        });
+        agent.authorization = Some(JwtAuthConfig {
+            jwks_uri: "https://auth.example/.well-known/jwks.json".to_string(),
+            userinfo_endpoint: None,
+            required_claims: HashMap::from([("iss".to_string(), serde_json::json!("constellation"))]),
+            skill_claims: HashMap::from([(
+                "test-skill".to_string(),
+                HashMap::from([("scope".to_string(), serde_json::json!(["write"]))]),
+            )]),
+            policy_ref: None,
+        });
+
+        let proto: pb::Agent = (&agent).into();
+        let roundtripped: Agent = proto.try_into().unwrap();
+        assert_eq!(roundtripped, agent);
+    }
+}
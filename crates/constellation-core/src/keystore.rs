@@ -0,0 +1,439 @@
+//! Pluggable key storage backends.
+//!
+//! [`KeyStorage`](crate::models::mcp::KeyStorage) only names *where* keys live;
+//! this module gives each variant actual behavior behind a [`KeyStore`] trait so
+//! callers can store and retrieve the key material referenced by `key_id` in
+//! `McpSignature`/`McpEncryptedMessage` instead of treating it as opaque.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use argon2::Argon2;
+use rand::rngs::OsRng;
+use rand::RngCore;
+use zeroize::Zeroizing;
+
+use crate::models::mcp::{KeyManagement, KeyStorage};
+
+/// Length of an AES-256-GCM nonce, in bytes.
+const NONCE_LEN: usize = 12;
+
+/// Error returned by [`KeyStore`] operations.
+#[derive(Debug)]
+pub enum KeyStoreError {
+    /// No key is stored under the requested id.
+    NotFound(String),
+    /// The key material could not be sealed/unsealed (wrong passphrase, corruption).
+    Seal(String),
+    /// An underlying backend (disk, HSM, KMS) returned an error.
+    Backend(String),
+}
+
+impl std::fmt::Display for KeyStoreError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KeyStoreError::NotFound(id) => write!(f, "no key stored under id {id}"),
+            KeyStoreError::Seal(msg) => write!(f, "seal/unseal failure: {msg}"),
+            KeyStoreError::Backend(msg) => write!(f, "key store backend error: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for KeyStoreError {}
+
+/// Raw key material, always held in zeroizing memory so it is wiped on drop.
+pub type KeyBytes = Zeroizing<Vec<u8>>;
+
+/// A storage backend for cryptographic key material.
+pub trait KeyStore: Send + Sync {
+    /// Persist `key` under `key_id`, overwriting any existing entry.
+    fn store(&self, key_id: &str, key: KeyBytes) -> Result<(), KeyStoreError>;
+
+    /// Retrieve the key material stored under `key_id`.
+    fn fetch(&self, key_id: &str) -> Result<KeyBytes, KeyStoreError>;
+
+    /// Remove the key stored under `key_id`. Removing an absent key is not an error.
+    fn delete(&self, key_id: &str) -> Result<(), KeyStoreError>;
+
+    /// List all key ids currently held.
+    fn list_key_ids(&self) -> Result<Vec<String>, KeyStoreError>;
+
+    /// Wrap (encrypt) `key` for export, producing opaque sealed bytes.
+    fn wrap(&self, key: &[u8]) -> Result<Vec<u8>, KeyStoreError>;
+
+    /// Unwrap (decrypt) previously [`wrap`](KeyStore::wrap)ped bytes.
+    fn unwrap(&self, wrapped: &[u8]) -> Result<KeyBytes, KeyStoreError>;
+}
+
+/// Volatile in-memory key store. Keys never touch disk and are zeroized on drop.
+#[derive(Default)]
+pub struct InMemoryKeyStore {
+    keys: Mutex<HashMap<String, Vec<u8>>>,
+}
+
+impl InMemoryKeyStore {
+    /// Create an empty in-memory store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Drop for InMemoryKeyStore {
+    fn drop(&mut self) {
+        if let Ok(mut keys) = self.keys.lock() {
+            for (_, material) in keys.iter_mut() {
+                zeroize::Zeroize::zeroize(material);
+            }
+        }
+    }
+}
+
+impl KeyStore for InMemoryKeyStore {
+    fn store(&self, key_id: &str, key: KeyBytes) -> Result<(), KeyStoreError> {
+        self.keys
+            .lock()
+            .map_err(|_| KeyStoreError::Backend("lock poisoned".into()))?
+            .insert(key_id.to_string(), key.to_vec());
+        Ok(())
+    }
+
+    fn fetch(&self, key_id: &str) -> Result<KeyBytes, KeyStoreError> {
+        self.keys
+            .lock()
+            .map_err(|_| KeyStoreError::Backend("lock poisoned".into()))?
+            .get(key_id)
+            .map(|k| Zeroizing::new(k.clone()))
+            .ok_or_else(|| KeyStoreError::NotFound(key_id.to_string()))
+    }
+
+    fn delete(&self, key_id: &str) -> Result<(), KeyStoreError> {
+        if let Some(mut material) = self
+            .keys
+            .lock()
+            .map_err(|_| KeyStoreError::Backend("lock poisoned".into()))?
+            .remove(key_id)
+        {
+            zeroize::Zeroize::zeroize(&mut material);
+        }
+        Ok(())
+    }
+
+    fn list_key_ids(&self) -> Result<Vec<String>, KeyStoreError> {
+        Ok(self
+            .keys
+            .lock()
+            .map_err(|_| KeyStoreError::Backend("lock poisoned".into()))?
+            .keys()
+            .cloned()
+            .collect())
+    }
+
+    fn wrap(&self, key: &[u8]) -> Result<Vec<u8>, KeyStoreError> {
+        // An in-memory store has no wrapping key; return the bytes verbatim.
+        Ok(key.to_vec())
+    }
+
+    fn unwrap(&self, wrapped: &[u8]) -> Result<KeyBytes, KeyStoreError> {
+        Ok(Zeroizing::new(wrapped.to_vec()))
+    }
+}
+
+/// Keys sealed on disk under a master key derived from a passphrase with Argon2id.
+pub struct EncryptedDiskKeyStore {
+    dir: PathBuf,
+    master_key: Zeroizing<[u8; 32]>,
+}
+
+impl EncryptedDiskKeyStore {
+    /// Open (or create) an encrypted store rooted at `dir`, deriving the master
+    /// key from `passphrase` and a per-store `salt` via Argon2id.
+    pub fn open(
+        dir: impl Into<PathBuf>,
+        passphrase: &[u8],
+        salt: &[u8],
+    ) -> Result<Self, KeyStoreError> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir).map_err(|e| KeyStoreError::Backend(e.to_string()))?;
+        let mut master_key = Zeroizing::new([0u8; 32]);
+        Argon2::default()
+            .hash_password_into(passphrase, salt, master_key.as_mut())
+            .map_err(|e| KeyStoreError::Seal(e.to_string()))?;
+        Ok(Self { dir, master_key })
+    }
+
+    fn path_for(&self, key_id: &str) -> PathBuf {
+        // Key ids are opaque; hex-encode to keep them filesystem-safe.
+        self.dir.join(format!("{}.sealed", hex::encode(key_id)))
+    }
+
+    /// AES-256-GCM seal under the master key, with a fresh random nonce
+    /// prepended to the ciphertext so [`Self::unseal`] can recover it.
+    fn seal(&self, plaintext: &[u8]) -> Result<Vec<u8>, KeyStoreError> {
+        let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(self.master_key.as_ref()));
+        let mut nonce_bytes = [0u8; NONCE_LEN];
+        OsRng.fill_bytes(&mut nonce_bytes);
+        let ciphertext = cipher
+            .encrypt(Nonce::from_slice(&nonce_bytes), plaintext)
+            .map_err(|e| KeyStoreError::Seal(e.to_string()))?;
+        let mut sealed = nonce_bytes.to_vec();
+        sealed.extend(ciphertext);
+        Ok(sealed)
+    }
+
+    /// Inverse of [`Self::seal`]: splits off the leading nonce and decrypts
+    /// the remainder, failing closed if the AEAD tag does not verify (wrong
+    /// master key or tampered/corrupted ciphertext).
+    fn unseal(&self, sealed: &[u8]) -> Result<Vec<u8>, KeyStoreError> {
+        if sealed.len() < NONCE_LEN {
+            return Err(KeyStoreError::Seal("sealed data shorter than nonce".into()));
+        }
+        let (nonce_bytes, ciphertext) = sealed.split_at(NONCE_LEN);
+        let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(self.master_key.as_ref()));
+        cipher
+            .decrypt(Nonce::from_slice(nonce_bytes), ciphertext)
+            .map_err(|_| KeyStoreError::Seal("authentication failed".into()))
+    }
+}
+
+impl KeyStore for EncryptedDiskKeyStore {
+    fn store(&self, key_id: &str, key: KeyBytes) -> Result<(), KeyStoreError> {
+        let sealed = self.seal(&key)?;
+        std::fs::write(self.path_for(key_id), sealed)
+            .map_err(|e| KeyStoreError::Backend(e.to_string()))
+    }
+
+    fn fetch(&self, key_id: &str) -> Result<KeyBytes, KeyStoreError> {
+        let path = self.path_for(key_id);
+        if !path.exists() {
+            return Err(KeyStoreError::NotFound(key_id.to_string()));
+        }
+        let sealed = std::fs::read(&path).map_err(|e| KeyStoreError::Backend(e.to_string()))?;
+        Ok(Zeroizing::new(self.unseal(&sealed)?))
+    }
+
+    fn delete(&self, key_id: &str) -> Result<(), KeyStoreError> {
+        let path = self.path_for(key_id);
+        if path.exists() {
+            std::fs::remove_file(path).map_err(|e| KeyStoreError::Backend(e.to_string()))?;
+        }
+        Ok(())
+    }
+
+    fn list_key_ids(&self) -> Result<Vec<String>, KeyStoreError> {
+        let mut ids = Vec::new();
+        for entry in std::fs::read_dir(&self.dir).map_err(|e| KeyStoreError::Backend(e.to_string()))?
+        {
+            let entry = entry.map_err(|e| KeyStoreError::Backend(e.to_string()))?;
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            if let Some(stem) = name.strip_suffix(".sealed") {
+                if let Ok(bytes) = hex::decode(stem) {
+                    if let Ok(id) = String::from_utf8(bytes) {
+                        ids.push(id);
+                    }
+                }
+            }
+        }
+        Ok(ids)
+    }
+
+    fn wrap(&self, key: &[u8]) -> Result<Vec<u8>, KeyStoreError> {
+        self.seal(key)
+    }
+
+    fn unwrap(&self, wrapped: &[u8]) -> Result<KeyBytes, KeyStoreError> {
+        Ok(Zeroizing::new(self.unseal(wrapped)?))
+    }
+}
+
+/// External key-management provider invoked by the HSM/KMS stores.
+///
+/// Concrete deployments implement this against a PKCS#11 module, a cloud KMS
+/// SDK, etc. The key stores below delegate sealing and storage to it.
+pub trait KeyProvider: Send + Sync {
+    fn store(&self, key_id: &str, key: &[u8]) -> Result<(), KeyStoreError>;
+    fn fetch(&self, key_id: &str) -> Result<KeyBytes, KeyStoreError>;
+    fn delete(&self, key_id: &str) -> Result<(), KeyStoreError>;
+    fn list_key_ids(&self) -> Result<Vec<String>, KeyStoreError>;
+    fn wrap(&self, key: &[u8]) -> Result<Vec<u8>, KeyStoreError>;
+    fn unwrap(&self, wrapped: &[u8]) -> Result<KeyBytes, KeyStoreError>;
+}
+
+/// Key store backed by a hardware security module via a [`KeyProvider`].
+pub struct HsmKeyStore {
+    provider: Box<dyn KeyProvider>,
+}
+
+impl HsmKeyStore {
+    /// Create an HSM-backed store delegating to `provider`.
+    pub fn new(provider: Box<dyn KeyProvider>) -> Self {
+        Self { provider }
+    }
+}
+
+/// Key store backed by a cloud KMS via a [`KeyProvider`].
+pub struct CloudKmsKeyStore {
+    provider: Box<dyn KeyProvider>,
+}
+
+impl CloudKmsKeyStore {
+    /// Create a cloud-KMS-backed store delegating to `provider`.
+    pub fn new(provider: Box<dyn KeyProvider>) -> Self {
+        Self { provider }
+    }
+}
+
+macro_rules! delegate_keystore {
+    ($ty:ty) => {
+        impl KeyStore for $ty {
+            fn store(&self, key_id: &str, key: KeyBytes) -> Result<(), KeyStoreError> {
+                self.provider.store(key_id, &key)
+            }
+            fn fetch(&self, key_id: &str) -> Result<KeyBytes, KeyStoreError> {
+                self.provider.fetch(key_id)
+            }
+            fn delete(&self, key_id: &str) -> Result<(), KeyStoreError> {
+                self.provider.delete(key_id)
+            }
+            fn list_key_ids(&self) -> Result<Vec<String>, KeyStoreError> {
+                self.provider.list_key_ids()
+            }
+            fn wrap(&self, key: &[u8]) -> Result<Vec<u8>, KeyStoreError> {
+                self.provider.wrap(key)
+            }
+            fn unwrap(&self, wrapped: &[u8]) -> Result<KeyBytes, KeyStoreError> {
+                self.provider.unwrap(wrapped)
+            }
+        }
+    };
+}
+
+delegate_keystore!(HsmKeyStore);
+delegate_keystore!(CloudKmsKeyStore);
+
+impl KeyManagement {
+    /// Build a live [`KeyStore`] matching this configuration's `storage` setting.
+    ///
+    /// Only `Memory` is fully self-contained: `EncryptedDisk` needs a
+    /// caller-supplied passphrase (use
+    /// [`key_store_with_passphrase`](Self::key_store_with_passphrase)) and
+    /// `Hsm`/`CloudKms` need a [`KeyProvider`] (use
+    /// [`key_store_with_provider`](Self::key_store_with_provider)) — a shared
+    /// default for either would hand every caller the same key material.
+    pub fn key_store(&self) -> Result<Box<dyn KeyStore>, KeyStoreError> {
+        match self.storage {
+            KeyStorage::Memory => Ok(Box::new(InMemoryKeyStore::new())),
+            KeyStorage::EncryptedDisk => Err(KeyStoreError::Backend(
+                "EncryptedDisk storage requires a passphrase; use key_store_with_passphrase".into(),
+            )),
+            KeyStorage::Hsm | KeyStorage::CloudKms => Err(KeyStoreError::Backend(
+                "HSM/CloudKMS storage requires a KeyProvider; use key_store_with_provider".into(),
+            )),
+        }
+    }
+
+    /// Build an [`EncryptedDiskKeyStore`] rooted at `dir`, deriving its master
+    /// key from `passphrase` and `salt` via Argon2id. Each distinct
+    /// passphrase/salt pair yields an independent key store.
+    pub fn key_store_with_passphrase(
+        &self,
+        dir: impl Into<PathBuf>,
+        passphrase: &[u8],
+        salt: &[u8],
+    ) -> Result<Box<dyn KeyStore>, KeyStoreError> {
+        match self.storage {
+            KeyStorage::EncryptedDisk => {
+                Ok(Box::new(EncryptedDiskKeyStore::open(dir, passphrase, salt)?))
+            }
+            _ => Err(KeyStoreError::Backend(
+                "key_store_with_passphrase only applies to EncryptedDisk storage".into(),
+            )),
+        }
+    }
+
+    /// Build a [`KeyStore`] for `Hsm`/`CloudKms` storage backed by `provider`.
+    pub fn key_store_with_provider(
+        &self,
+        provider: Box<dyn KeyProvider>,
+    ) -> Box<dyn KeyStore> {
+        match self.storage {
+            KeyStorage::CloudKms => Box::new(CloudKmsKeyStore::new(provider)),
+            // Default HSM and everything else to the HSM delegate.
+            _ => Box::new(HsmKeyStore::new(provider)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_in_memory_store_roundtrip() {
+        let store = InMemoryKeyStore::new();
+        store
+            .store("key-1", Zeroizing::new(vec![1, 2, 3, 4]))
+            .unwrap();
+        assert_eq!(store.fetch("key-1").unwrap().to_vec(), vec![1, 2, 3, 4]);
+        assert_eq!(store.list_key_ids().unwrap(), vec!["key-1".to_string()]);
+        store.delete("key-1").unwrap();
+        assert!(matches!(
+            store.fetch("key-1"),
+            Err(KeyStoreError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_wrap_unwrap_roundtrip() {
+        let store = InMemoryKeyStore::new();
+        let wrapped = store.wrap(&[9, 8, 7]).unwrap();
+        assert_eq!(store.unwrap(&wrapped).unwrap().to_vec(), vec![9, 8, 7]);
+    }
+
+    #[test]
+    fn test_encrypted_disk_store_roundtrip() {
+        let dir = std::env::temp_dir().join(format!("constellation-keystore-test-{}", std::process::id()));
+        let store = EncryptedDiskKeyStore::open(&dir, b"correct horse battery staple", b"test-salt").unwrap();
+        store.store("key-1", Zeroizing::new(vec![1, 2, 3, 4])).unwrap();
+        assert_eq!(store.fetch("key-1").unwrap().to_vec(), vec![1, 2, 3, 4]);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_encrypted_disk_store_detects_tampering() {
+        let dir = std::env::temp_dir().join(format!("constellation-keystore-test-tamper-{}", std::process::id()));
+        let store = EncryptedDiskKeyStore::open(&dir, b"correct horse battery staple", b"test-salt").unwrap();
+        store.store("key-1", Zeroizing::new(vec![1, 2, 3, 4])).unwrap();
+
+        let path = store.path_for("key-1");
+        let mut sealed = std::fs::read(&path).unwrap();
+        let last = sealed.len() - 1;
+        sealed[last] ^= 0xFF;
+        std::fs::write(&path, sealed).unwrap();
+
+        assert!(matches!(store.fetch("key-1"), Err(KeyStoreError::Seal(_))));
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_different_passphrases_yield_independent_keys() {
+        let dir_a = std::env::temp_dir().join(format!("constellation-keystore-test-a-{}", std::process::id()));
+        let dir_b = std::env::temp_dir().join(format!("constellation-keystore-test-b-{}", std::process::id()));
+        let store_a = EncryptedDiskKeyStore::open(&dir_a, b"passphrase-a", b"salt").unwrap();
+        let store_b = EncryptedDiskKeyStore::open(&dir_b, b"passphrase-b", b"salt").unwrap();
+
+        store_a.store("key-1", Zeroizing::new(vec![5, 6, 7, 8])).unwrap();
+        let sealed = std::fs::read(store_a.path_for("key-1")).unwrap();
+        std::fs::write(store_b.path_for("key-1"), sealed).unwrap();
+
+        assert!(matches!(
+            store_b.fetch("key-1"),
+            Err(KeyStoreError::Seal(_))
+        ));
+        std::fs::remove_dir_all(&dir_a).ok();
+        std::fs::remove_dir_all(&dir_b).ok();
+    }
+}
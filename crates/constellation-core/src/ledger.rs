@@ -0,0 +1,433 @@
+//! Round-based reputation and reward ledger backing
+//! [`CoordinationStrategyType::MarketBased`](crate::models::hybrid_agent::CoordinationStrategyType::MarketBased)
+//! and [`DecisionMakingApproach::Auction`](crate::models::hybrid_agent::DecisionMakingApproach::Auction) /
+//! [`Voting`](crate::models::hybrid_agent::DecisionMakingApproach::Voting).
+//!
+//! Those strategy types presuppose executors compete for work and are
+//! rewarded or penalized on their track record, but nothing accumulates that
+//! track record. [`ExecutorLedger`] divides runtime into rounds: executors
+//! [`record`](ExecutorLedger::record) task outcomes as they complete, and
+//! [`close_round`](ExecutorLedger::close_round) folds the round's realized
+//! `success_rate` and `quality_score` into a persistent reputation score via
+//! an EWMA, distributes a reward pool proportional to reputation-weighted
+//! contribution, and slashes the bond of any executor whose latest
+//! `error_rate`/`availability` breached the configured thresholds. Auction
+//! and market-based decision-making then call
+//! [`discounted_bid`](ExecutorLedger::discounted_bid) to discount an
+//! executor's `cost_per_1k_tasks` by its reputation when ranking bids.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::models::hybrid_agent::{BudgetAllocation, ExecutorPerformance, HybridAgentConfig};
+
+/// Tunable parameters for the ledger.
+#[derive(Debug, Clone)]
+pub struct LedgerParams {
+    /// EWMA smoothing weight applied to each round's composite score when
+    /// folding it into the persistent reputation (closer to 1.0 means the
+    /// latest round dominates).
+    pub reputation_alpha: f64,
+    /// Weight of a round's success rate in its composite score.
+    pub w_success: f64,
+    /// Weight of a round's average quality score in its composite score.
+    pub w_quality: f64,
+    /// Reward pool distributed at each round close.
+    pub reward_pool_per_round: f64,
+    /// Error rate above which an executor's bond is slashed.
+    pub error_rate_threshold: f64,
+    /// Availability below which an executor's bond is slashed.
+    pub availability_threshold: f64,
+    /// Fraction of an offending executor's bond slashed per breach.
+    pub slash_fraction: f64,
+    /// Weight applied to reputation when discounting a bid (0 = no discount).
+    pub bid_discount_weight: f64,
+}
+
+impl Default for LedgerParams {
+    fn default() -> Self {
+        Self {
+            reputation_alpha: 0.3,
+            w_success: 0.6,
+            w_quality: 0.4,
+            reward_pool_per_round: 100.0,
+            error_rate_threshold: 0.1,
+            availability_threshold: 0.95,
+            slash_fraction: 0.2,
+            bid_discount_weight: 0.5,
+        }
+    }
+}
+
+/// The realized outcome of one completed task, reported to [`ExecutorLedger::record`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RoundTaskOutcome {
+    /// Whether the task succeeded.
+    pub success: bool,
+    /// Realized quality score (0.0–1.0).
+    pub quality_score: f64,
+}
+
+/// Per-round accumulation for one executor, reset on [`ExecutorLedger::begin_round`].
+#[derive(Debug, Clone, Default)]
+struct RoundAccumulator {
+    served_tasks: u64,
+    successes: u64,
+    quality_sum: f64,
+}
+
+/// Persistent state for one executor across rounds.
+#[derive(Debug, Clone)]
+struct ExecutorAccount {
+    reputation: f64,
+    bond: f64,
+    latest_performance: Option<ExecutorPerformance>,
+    round: RoundAccumulator,
+}
+
+impl Default for ExecutorAccount {
+    fn default() -> Self {
+        Self {
+            // Neutral starting reputation so a brand-new executor is neither
+            // favored nor shut out of its first round's reward split.
+            reputation: 0.5,
+            bond: 0.0,
+            latest_performance: None,
+            round: RoundAccumulator::default(),
+        }
+    }
+}
+
+/// One executor's outcome from a closed round, for audit/inspection.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ExecutorRoundEntry {
+    /// Executor this entry reports on.
+    pub executor_id: String,
+    /// Tasks served during the round.
+    pub served_tasks: u64,
+    /// Realized success rate over the round (`NaN`-free; 0.0 if no tasks served).
+    pub success_rate: f64,
+    /// Average realized quality score over the round.
+    pub quality_score: f64,
+    /// Reputation after folding in this round's composite score.
+    pub reputation: f64,
+    /// Share of `reward_pool_per_round` distributed to this executor.
+    pub reward: f64,
+    /// Remaining bond after any slash applied this round.
+    pub bond_after: f64,
+    /// Whether the executor's bond was slashed this round.
+    pub slashed: bool,
+}
+
+/// Report produced by [`ExecutorLedger::close_round`], serializable for audit.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RoundReport {
+    /// Index of the round that just closed (0-based).
+    pub round_index: u64,
+    /// When the round was closed.
+    pub closed_at: DateTime<Utc>,
+    /// Per-executor outcome of the round.
+    pub entries: Vec<ExecutorRoundEntry>,
+}
+
+/// Round-based reputation, bond, and reward accounting for competing executors.
+pub struct ExecutorLedger {
+    params: LedgerParams,
+    accounts: HashMap<String, ExecutorAccount>,
+    round_index: u64,
+}
+
+impl ExecutorLedger {
+    /// Build a ledger seeded with every executor in `config`, each starting
+    /// with a neutral reputation and no bond posted.
+    pub fn from_config(config: &HybridAgentConfig, params: LedgerParams) -> Self {
+        let mut accounts = HashMap::new();
+        for executor in &config.executors {
+            accounts.insert(executor.id.clone(), ExecutorAccount::default());
+        }
+        Self {
+            params,
+            accounts,
+            round_index: 0,
+        }
+    }
+
+    /// Reset every executor's per-round accumulator; call at the start of a
+    /// new round (the first round is implicitly open once the ledger is
+    /// built).
+    pub fn begin_round(&mut self) {
+        for account in self.accounts.values_mut() {
+            account.round = RoundAccumulator::default();
+        }
+    }
+
+    /// Post (or top up) `executor_id`'s bond, drawn from its share of
+    /// [`BudgetAllocation`].
+    pub fn post_bond(&mut self, executor_id: &str, amount: f64, budget: &BudgetAllocation) {
+        let cap = budget.total_budget * budget.executors_percentage / 100.0;
+        let account = self.accounts.entry(executor_id.to_string()).or_default();
+        let posted = account.bond + amount;
+        account.bond = if cap > 0.0 { posted.min(cap) } else { posted };
+    }
+
+    /// Record one completed task's outcome for `executor_id`.
+    pub fn record(&mut self, executor_id: &str, outcome: RoundTaskOutcome) {
+        let account = self.accounts.entry(executor_id.to_string()).or_default();
+        account.round.served_tasks += 1;
+        if outcome.success {
+            account.round.successes += 1;
+        }
+        account.round.quality_sum += outcome.quality_score;
+    }
+
+    /// Record the executor's latest live performance snapshot, consulted at
+    /// [`close_round`](Self::close_round) to decide whether its bond breaches
+    /// the `error_rate`/`availability` thresholds.
+    pub fn report_performance(&mut self, executor_id: &str, performance: ExecutorPerformance) {
+        let account = self.accounts.entry(executor_id.to_string()).or_default();
+        account.latest_performance = Some(performance);
+    }
+
+    /// Close the current round: fold each executor's realized success rate
+    /// and quality score into its persistent reputation (EWMA), distribute
+    /// `reward_pool_per_round` proportional to reputation-weighted tasks
+    /// served, slash bonds for threshold breaches, then advance to the next
+    /// round.
+    pub fn close_round(&mut self) -> RoundReport {
+        let p = &self.params;
+
+        // Composite score and reputation update, computed before the reward
+        // split so rewards reflect the freshly updated reputation.
+        let mut composite: HashMap<String, (u64, f64, f64)> = HashMap::new(); // id -> (served, success_rate, quality)
+        for (id, account) in self.accounts.iter_mut() {
+            let served = account.round.served_tasks;
+            let success_rate = if served > 0 {
+                account.round.successes as f64 / served as f64
+            } else {
+                0.0
+            };
+            let quality = if served > 0 {
+                account.round.quality_sum / served as f64
+            } else {
+                0.0
+            };
+            if served > 0 {
+                let score = p.w_success * success_rate + p.w_quality * quality;
+                account.reputation = p.reputation_alpha * score + (1.0 - p.reputation_alpha) * account.reputation;
+            }
+            composite.insert(id.clone(), (served, success_rate, quality));
+        }
+
+        let total_weight: f64 = composite
+            .iter()
+            .map(|(id, (served, _, _))| self.accounts[id].reputation * *served as f64)
+            .sum();
+
+        let mut entries = Vec::with_capacity(self.accounts.len());
+        for (id, (served, success_rate, quality)) in composite {
+            let account = self.accounts.get_mut(&id).expect("account seeded above");
+
+            let reward = if total_weight > 0.0 {
+                p.reward_pool_per_round * (account.reputation * served as f64) / total_weight
+            } else {
+                0.0
+            };
+
+            let breached = account
+                .latest_performance
+                .as_ref()
+                .map(|perf| perf.error_rate > p.error_rate_threshold || perf.availability < p.availability_threshold)
+                .unwrap_or(false);
+            let slashed = breached && account.bond > 0.0;
+            if slashed {
+                account.bond *= 1.0 - p.slash_fraction;
+            }
+
+            entries.push(ExecutorRoundEntry {
+                executor_id: id,
+                served_tasks: served,
+                success_rate,
+                quality_score: quality,
+                reputation: account.reputation,
+                reward,
+                bond_after: account.bond,
+                slashed,
+            });
+        }
+        entries.sort_by(|a, b| a.executor_id.cmp(&b.executor_id));
+
+        let report = RoundReport {
+            round_index: self.round_index,
+            closed_at: Utc::now(),
+            entries,
+        };
+        self.round_index += 1;
+        self.begin_round();
+        report
+    }
+
+    /// Current reputation for `executor_id` (neutral `0.5` if unseen).
+    pub fn reputation(&self, executor_id: &str) -> f64 {
+        self.accounts
+            .get(executor_id)
+            .map(|a| a.reputation)
+            .unwrap_or(0.5)
+    }
+
+    /// Current bond posted by `executor_id` (`0.0` if unseen or never posted).
+    pub fn bond(&self, executor_id: &str) -> f64 {
+        self.accounts.get(executor_id).map(|a| a.bond).unwrap_or(0.0)
+    }
+
+    /// `cost_per_1k_tasks` discounted by `executor_id`'s reputation, for
+    /// ranking bids under [`DecisionMakingApproach::Auction`](crate::models::hybrid_agent::DecisionMakingApproach::Auction).
+    /// A reputation of `1.0` yields the maximum discount
+    /// (`bid_discount_weight`); a reputation of `0.0` applies none.
+    pub fn discounted_bid(&self, executor_id: &str, cost_per_1k_tasks: f64) -> f64 {
+        let reputation = self.reputation(executor_id);
+        let discount = (self.params.bid_discount_weight * reputation).clamp(0.0, 1.0);
+        cost_per_1k_tasks * (1.0 - discount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::hybrid_agent::{ExecutorConfig, ExecutorDomain, HybridAgentConfig};
+
+    fn ledger_for(ids: &[&str]) -> ExecutorLedger {
+        let mut config = HybridAgentConfig::new("agent".to_string(), "desc".to_string());
+        for id in ids {
+            config
+                .executors
+                .push(ExecutorConfig::new(id.to_string(), ExecutorDomain::CodeGeneration));
+        }
+        ExecutorLedger::from_config(&config, LedgerParams::default())
+    }
+
+    fn budget(total: f64, executors_percentage: f64) -> BudgetAllocation {
+        BudgetAllocation {
+            total_budget: total,
+            strategist_percentage: 0.0,
+            executors_percentage,
+            infrastructure_percentage: 0.0,
+            reserve_percentage: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_reputation_and_bond_default_for_unseen_executor() {
+        let ledger = ledger_for(&[]);
+        assert_eq!(ledger.reputation("ghost"), 0.5);
+        assert_eq!(ledger.bond("ghost"), 0.0);
+    }
+
+    #[test]
+    fn test_close_round_folds_composite_score_into_reputation_via_ewma() {
+        let mut ledger = ledger_for(&["exec-1"]);
+        for _ in 0..3 {
+            ledger.record("exec-1", RoundTaskOutcome {
+                success: true,
+                quality_score: 1.0,
+            });
+        }
+
+        let report = ledger.close_round();
+        assert_eq!(report.entries.len(), 1);
+        assert_eq!(report.entries[0].success_rate, 1.0);
+        assert_eq!(report.entries[0].quality_score, 1.0);
+        // 0.3 * (0.6 * 1.0 + 0.4 * 1.0) + 0.7 * 0.5 (neutral starting reputation)
+        assert_eq!(report.entries[0].reputation, 0.65);
+        assert_eq!(ledger.reputation("exec-1"), 0.65);
+    }
+
+    #[test]
+    fn test_close_round_distributes_reward_proportional_to_reputation_weighted_tasks() {
+        let mut ledger = ledger_for(&["good", "bad"]);
+        ledger.record("good", RoundTaskOutcome {
+            success: true,
+            quality_score: 1.0,
+        });
+        ledger.record("bad", RoundTaskOutcome {
+            success: false,
+            quality_score: 0.0,
+        });
+
+        let report = ledger.close_round();
+        let good = report.entries.iter().find(|e| e.executor_id == "good").unwrap();
+        let bad = report.entries.iter().find(|e| e.executor_id == "bad").unwrap();
+
+        assert_eq!(good.reward, 65.0);
+        assert_eq!(bad.reward, 35.0);
+        assert_eq!(good.reward + bad.reward, 100.0);
+    }
+
+    #[test]
+    fn test_close_round_slashes_bond_on_threshold_breach() {
+        let mut ledger = ledger_for(&["exec-1"]);
+        let budget = budget(1000.0, 50.0);
+        ledger.post_bond("exec-1", 100.0, &budget);
+        ledger.report_performance(
+            "exec-1",
+            ExecutorPerformance {
+                throughput_tps: 1.0,
+                avg_latency_ms: 0,
+                p95_latency_ms: 0,
+                p99_latency_ms: 0,
+                error_rate: 0.2,
+                availability: 0.99,
+                cost_per_1k_tasks: 1.0,
+            },
+        );
+
+        let report = ledger.close_round();
+        assert!(report.entries[0].slashed);
+        assert_eq!(report.entries[0].bond_after, 80.0);
+        assert_eq!(ledger.bond("exec-1"), 80.0);
+    }
+
+    #[test]
+    fn test_close_round_does_not_slash_a_healthy_executor() {
+        let mut ledger = ledger_for(&["exec-1"]);
+        let budget = budget(1000.0, 50.0);
+        ledger.post_bond("exec-1", 100.0, &budget);
+        ledger.report_performance(
+            "exec-1",
+            ExecutorPerformance {
+                throughput_tps: 1.0,
+                avg_latency_ms: 0,
+                p95_latency_ms: 0,
+                p99_latency_ms: 0,
+                error_rate: 0.01,
+                availability: 0.999,
+                cost_per_1k_tasks: 1.0,
+            },
+        );
+
+        let report = ledger.close_round();
+        assert!(!report.entries[0].slashed);
+        assert_eq!(report.entries[0].bond_after, 100.0);
+    }
+
+    #[test]
+    fn test_post_bond_caps_at_the_budget_allocations_executor_share() {
+        let mut ledger = ledger_for(&["exec-1"]);
+        let budget = budget(1000.0, 50.0);
+
+        ledger.post_bond("exec-1", 600.0, &budget);
+        assert_eq!(ledger.bond("exec-1"), 500.0);
+
+        ledger.post_bond("exec-1", 600.0, &budget);
+        assert_eq!(ledger.bond("exec-1"), 500.0);
+    }
+
+    #[test]
+    fn test_discounted_bid_scales_with_reputation() {
+        let ledger = ledger_for(&["exec-1"]);
+        // Neutral 0.5 reputation and the default 0.5 discount weight halve the
+        // maximum discount: 100 * (1 - 0.5 * 0.5) = 75.0.
+        assert_eq!(ledger.discounted_bid("exec-1", 100.0), 75.0);
+    }
+}
@@ -1,9 +1,37 @@
 //! Core types and utilities for the Constellation multi-agent platform.
 
+pub mod attestation;
+pub mod audit;
+pub mod autoscaler;
+pub mod dispatcher;
+pub mod escrow;
+pub mod fallback;
+pub mod grpc;
+pub mod keystore;
+pub mod ledger;
 pub mod models;
+pub mod reencrypt;
+pub mod rl;
+pub mod rotation;
+pub mod scheduler;
+pub mod telemetry;
 
 // Re-export common types for convenience.
-pub use models::agent::{Agent, AgentCapabilities, AgentContact, AgentInterface, AgentProvider, AgentSkill, ProtocolBinding, SecuritySchemeType};
-pub use models::dtg::{DataTransformationGraph, DtgNode, DtgDataRef, DtgNodeStatus, DtgMetrics, DtgEdge, DtgGraphStatus, DtgProvenance};
-pub use models::mcp::{McpSecurityContext, SecurityLevel, McpAlgorithms, KeyManagement, AccessControl, AuditLogging, McpSecureEnvelope, McpEncryptedMessage, McpSignature};
-pub use models::hybrid_agent::{HybridAgentConfig, StrategistConfig, ExecutorConfig, CoordinationStrategy, ResourceAllocation, PerformanceTargets};
+pub use models::agent::{Agent, AgentCapabilities, AgentContact, AgentInterface, AgentProvider, AgentSkill, ProtocolBinding, SecuritySchemeType, CapabilityNegotiation};
+pub use models::dtg::{DataTransformationGraph, DtgNode, DtgDataRef, DtgNodeStatus, DtgMetrics, DtgEdge, DtgGraphStatus, DtgProvenance, VerificationError};
+pub use models::mcp::{McpSecurityContext, SecurityLevel, McpAlgorithms, KeyManagement, AccessControl, AuditLogging, McpSecureEnvelope, McpEncryptedMessage, McpSignature, VerifyError};
+pub use models::hybrid_agent::{HybridAgentConfig, StrategistConfig, ExecutorConfig, CoordinationStrategy, ResourceAllocation, PerformanceTargets, AllocationResult, ExecutorAllocation, BindingConstraint};
+pub use models::security::{SecurityScheme, ApiKeyLocation, OAuth2Flows, OpenIdConfiguration};
+pub use models::localization::{LanguageTag, LocalizedText, ResolvedSkill};
+pub use models::remote_attestation::{AttestationError, AttestationEvidence, AttestationVerdict};
+pub use models::analysis::{Bottleneck, CriticalPath};
+pub use models::arrow_export::{graphs_to_record_batches, DtgArrowWriter, DtgRecordBatches};
+pub use models::migration::{load_versioned, MigrationError, MigrationRegistry, Versioned};
+pub use models::prov::ProvDocument;
+pub use autoscaler::{PredictiveAllocator, Sample, ScalingDecision};
+pub use dispatcher::{AdmissionGuard, ExecutorDispatcher, ResourceExhaustion};
+pub use fallback::{FallbackEvent, FallbackMonitor, FallbackSink, MetricsSource, MonitorHandle};
+pub use ledger::{ExecutorLedger, ExecutorRoundEntry, LedgerParams, RoundReport, RoundTaskOutcome};
+pub use rl::{RlCoordinator, RlHyperparams, TaskFeatures, TaskOutcome};
+pub use scheduler::{DtgScheduler, SchedulerError};
+pub use models::authz::{AuthzDecision, AuthzError, JwtAuthConfig, TokenChecker};
@@ -1,6 +1,11 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 use serde_json;
 
+use super::localization::{LocalizedList, LocalizedText};
+use super::security::SecurityScheme;
+
 /// A2A Protocol Binding types
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "UPPERCASE")]
@@ -29,6 +34,8 @@ pub enum SecuritySchemeType {
     OpenIdConnect,
     /// Mutual TLS security scheme
     MutualTls,
+    /// TEE remote-attestation security scheme
+    Attestation,
 }
 
 /// Agent skill representing a specific task the agent can perform
@@ -36,18 +43,23 @@ pub enum SecuritySchemeType {
 pub struct AgentSkill {
     /// Unique identifier for the skill
     pub id: String,
-    /// Human-readable name for the skill
-    pub name: String,
-    /// Detailed description of the skill
-    pub description: String,
+    /// Human-readable name for the skill. Serializes as a bare string when no
+    /// localized variants are set, so non-localized cards are unaffected.
+    pub name: LocalizedText,
+    /// Detailed description of the skill.
+    pub description: LocalizedText,
     /// Keywords describing the skill's capabilities
     pub tags: Vec<String>,
-    /// Example prompts or scenarios
-    pub examples: Option<Vec<String>>,
+    /// Example prompts or scenarios.
+    pub examples: Option<LocalizedList>,
     /// Supported input media types for this skill
     pub input_modes: Option<Vec<String>>,
     /// Supported output media types for this skill
     pub output_modes: Option<Vec<String>>,
+    /// Security requirements specific to this skill. Each map is an alternative
+    /// set of scheme-name → required-scopes; any one satisfied set authorizes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub security: Option<Vec<HashMap<String, Vec<String>>>>,
 }
 
 /// Supported interface for agent communication
@@ -110,8 +122,9 @@ pub struct Agent {
     pub id: String,
     /// Human-readable name of the agent
     pub name: String,
-    /// What the agent does
-    pub description: String,
+    /// What the agent does. Serializes as a bare string when no localized
+    /// variants are set, so non-localized cards are unaffected.
+    pub description: LocalizedText,
     /// Version of the A2A protocol this agent supports
     pub protocol_version: String,
     /// Version of the agent
@@ -136,6 +149,72 @@ pub struct Agent {
     pub documentation_url: Option<String>,
     /// URL to an icon for the agent
     pub icon_url: Option<String>,
+    /// Named security schemes this agent accepts, keyed by an arbitrary
+    /// scheme name referenced from `security` requirement maps.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub security_schemes: HashMap<String, SecurityScheme>,
+    /// Card-level security requirements. Each map is an alternative set of
+    /// scheme-name → required-scopes; any one satisfied set authorizes.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub security: Vec<HashMap<String, Vec<String>>>,
+    /// Range of A2A protocol versions this agent can speak, used for
+    /// negotiation with peers whose exact `protocol_version` may differ.
+    #[serde(default = "default_protocol_range")]
+    pub supported_protocol_range: semver::VersionReq,
+    /// TEE attestation evidence proving this agent runs in a trusted enclave,
+    /// verified against the card's `Attestation` security scheme.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attestation: Option<super::remote_attestation::AttestationEvidence>,
+    /// JWT bearer authorization declaration gating calls to this agent's skills.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub authorization: Option<super::authz::JwtAuthConfig>,
+    /// Persisted schema version of this serialized config, folded forward to the
+    /// current shape on load by the [`migration`](super::migration) registry.
+    #[serde(default = "super::migration::agent_schema_version")]
+    pub schema_version: u32,
+}
+
+/// Default supported protocol range: any `1.x` version.
+pub(crate) fn default_protocol_range() -> semver::VersionReq {
+    semver::VersionReq::parse(">=1.0.0, <2.0.0").expect("valid default version range")
+}
+
+/// A lightweight version + capabilities handshake exchanged before opening a
+/// session, so mismatched agents can fail fast instead of assuming `"1.0"`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CapabilityNegotiation {
+    /// The agent's own protocol version.
+    pub protocol_version: String,
+    /// The range of protocol versions the agent accepts.
+    pub supported_protocol_range: semver::VersionReq,
+    /// Broad capability tags (e.g. `streaming`, `push-notifications`,
+    /// `state-history`, plus Constellation-internal caps).
+    pub capabilities: Vec<String>,
+}
+
+/// Error returned when two agents share no compatible protocol version.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NoCompatibleVersion;
+
+impl std::fmt::Display for NoCompatibleVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "no compatible protocol version")
+    }
+}
+
+impl std::error::Error for NoCompatibleVersion {}
+
+/// Pad a bare `major.minor` (or `major`) protocol version to a full
+/// `major.minor.patch` so it parses as a [`semver::Version`]. Shared by
+/// [`Agent::protocol_semver`] and [`Agent::negotiate`] so a peer's bare
+/// version (e.g. the `"1.0"` default from [`Agent::new`]) is padded the
+/// same way ours is, instead of failing to parse.
+fn pad_bare_protocol_version(raw: &str) -> String {
+    match raw.split('.').count() {
+        1 => format!("{raw}.0.0"),
+        2 => format!("{raw}.0"),
+        _ => raw.to_string(),
+    }
 }
 
 impl Agent {
@@ -151,7 +230,7 @@ impl Agent {
         Self {
             id,
             name,
-            description,
+            description: description.into(),
             protocol_version: "1.0".to_string(),
             version: "1.0.0".to_string(),
             default_input_modes: vec!["text/plain".to_string(), "application/json".to_string()],
@@ -168,6 +247,12 @@ impl Agent {
             supports_extended_agent_card: Some(false),
             documentation_url: None,
             icon_url: None,
+            security_schemes: HashMap::new(),
+            security: Vec::new(),
+            supported_protocol_range: default_protocol_range(),
+            attestation: None,
+            authorization: None,
+            schema_version: super::migration::agent_schema_version(),
         }
     }
 
@@ -221,6 +306,176 @@ impl Agent {
     pub fn get_skill(&self, skill_id: &str) -> Option<&AgentSkill> {
         self.skills.iter().find(|skill| skill.id == skill_id)
     }
+
+    /// Register a named security scheme on the card.
+    pub fn add_security_scheme(&mut self, name: impl Into<String>, scheme: SecurityScheme) {
+        self.security_schemes.insert(name.into(), scheme);
+    }
+
+    /// Parse this agent's `protocol_version` into a [`semver::Version`].
+    pub fn protocol_semver(&self) -> Result<semver::Version, semver::Error> {
+        semver::Version::parse(&pad_bare_protocol_version(&self.protocol_version))
+    }
+
+    /// Negotiate the highest protocol version mutually supported by `self` and
+    /// `other`, or `None` if their ranges and versions don't overlap.
+    pub fn negotiate_version(&self, other: &Agent) -> Option<semver::Version> {
+        let ours = self.protocol_semver().ok()?;
+        let theirs = other.protocol_semver().ok()?;
+        // Both endpoints must accept the chosen version; prefer the higher of
+        // the two advertised versions.
+        let mut candidates = [ours, theirs];
+        candidates.sort();
+        candidates
+            .into_iter()
+            .rev()
+            .find(|v| self.supported_protocol_range.matches(v) && other.supported_protocol_range.matches(v))
+    }
+
+    /// Build a capability-negotiation handshake for this agent, pulling broad
+    /// capability tags from `capabilities` and Constellation metadata.
+    pub fn capability_negotiation(&self) -> CapabilityNegotiation {
+        let mut caps = Vec::new();
+        if self.capabilities.streaming == Some(true) {
+            caps.push("streaming".to_string());
+        }
+        if self.capabilities.push_notifications == Some(true) {
+            caps.push("push-notifications".to_string());
+        }
+        if self.capabilities.state_transition_history == Some(true) {
+            caps.push("state-history".to_string());
+        }
+        if let Some(metadata) = &self.metadata {
+            if let Some(internal) = metadata
+                .get("constellation")
+                .and_then(|c| c.get("capabilities"))
+                .and_then(|c| c.as_array())
+            {
+                caps.extend(internal.iter().filter_map(|v| v.as_str().map(String::from)));
+            }
+        }
+        CapabilityNegotiation {
+            protocol_version: self.protocol_version.clone(),
+            supported_protocol_range: self.supported_protocol_range.clone(),
+            capabilities: caps,
+        }
+    }
+
+    /// Compute the intersection of this agent's capabilities with a peer's
+    /// handshake, and the negotiated version. Fails fast when no protocol
+    /// version is mutually supported.
+    pub fn negotiate(
+        &self,
+        peer: &CapabilityNegotiation,
+    ) -> Result<(semver::Version, Vec<String>), NoCompatibleVersion> {
+        let ours = self.protocol_semver().map_err(|_| NoCompatibleVersion)?;
+        let theirs = semver::Version::parse(&pad_bare_protocol_version(&peer.protocol_version))
+            .map_err(|_| NoCompatibleVersion)?;
+        let mut candidates = [ours, theirs];
+        candidates.sort();
+        let version = candidates
+            .into_iter()
+            .rev()
+            .find(|v| self.supported_protocol_range.matches(v) && peer.supported_protocol_range.matches(v))
+            .ok_or(NoCompatibleVersion)?;
+
+        let mine = self.capability_negotiation().capabilities;
+        let shared = mine
+            .into_iter()
+            .filter(|c| peer.capabilities.contains(c))
+            .collect();
+        Ok((version, shared))
+    }
+
+    /// Merge card-level and skill-level security requirements for `skill_id`.
+    ///
+    /// Returns the set of alternative requirement maps a client must satisfy
+    /// before invoking the skill: skill-level requirements override the
+    /// card-level default when present, otherwise the card-level list applies.
+    /// Returns `None` if the skill does not exist.
+    pub fn required_schemes_for_skill(
+        &self,
+        skill_id: &str,
+    ) -> Option<Vec<HashMap<String, Vec<String>>>> {
+        let skill = self.get_skill(skill_id)?;
+        Some(match &skill.security {
+            Some(skill_security) => skill_security.clone(),
+            None => self.security.clone(),
+        })
+    }
+
+    /// Resolve the attestation endpoint, expected measurement, and evidence
+    /// this card would submit for verification, or the reason it can't.
+    /// Split out from [`verify_attestation`](Self::verify_attestation) so the
+    /// synchronous precondition checks stay unit-testable without an async
+    /// executor.
+    fn resolve_attestation_target(
+        &self,
+    ) -> Result<
+        (&str, &str, &super::remote_attestation::AttestationEvidence),
+        super::remote_attestation::AttestationError,
+    > {
+        use super::remote_attestation::AttestationError;
+        let (endpoint, expected) = self
+            .security_schemes
+            .values()
+            .find_map(|scheme| match scheme {
+                SecurityScheme::Attestation {
+                    attestation_endpoint,
+                    expected_measurement,
+                } => Some((attestation_endpoint.as_str(), expected_measurement.as_str())),
+                _ => None,
+            })
+            .ok_or(AttestationError::NoAttestationScheme)?;
+        let evidence = self
+            .attestation
+            .as_ref()
+            .ok_or(AttestationError::MissingEvidence)?;
+        Ok((endpoint, expected, evidence))
+    }
+
+    /// Verify this agent's TEE attestation evidence against the measurement
+    /// policy of its declared `Attestation` security scheme.
+    ///
+    /// Submits [`attestation`](Self::attestation) to the scheme's attestation
+    /// endpoint and checks the returned token against the expected measurement.
+    /// The resulting [`AttestationVerdict`](super::remote_attestation::AttestationVerdict)
+    /// gates whether the agent's `supports_extended_agent_card` data may be
+    /// trusted for sensitive skill dispatch.
+    pub async fn verify_attestation(
+        &self,
+    ) -> Result<super::remote_attestation::AttestationVerdict, super::remote_attestation::AttestationError>
+    {
+        let (endpoint, expected, evidence) = self.resolve_attestation_target()?;
+        super::remote_attestation::verify_evidence(endpoint, expected, evidence).await
+    }
+
+    /// Authorize a bearer `token` for the skill `skill_id`, validating the
+    /// token against the card's JWT authorization declaration and enforcing its
+    /// required claims. Returns a deny decision when the agent declares no
+    /// authorization, or when the skill is unknown.
+    ///
+    /// Reuses the process-wide [`TokenChecker`](super::authz::TokenChecker)
+    /// for this card's JWKS endpoint via
+    /// [`TokenChecker::shared`](super::authz::TokenChecker::shared), so the
+    /// JWKS cache is actually shared across calls instead of being rebuilt
+    /// (and re-fetched) on every invocation.
+    pub async fn authorize(&self, token: &str, skill_id: &str) -> super::authz::AuthzDecision {
+        use super::authz::{AuthzDecision, TokenChecker};
+        let Some(config) = &self.authorization else {
+            return AuthzDecision {
+                allowed: false,
+                reason: "agent declares no authorization scheme".to_string(),
+            };
+        };
+        if !self.has_skill(skill_id) {
+            return AuthzDecision {
+                allowed: false,
+                reason: format!("unknown skill `{skill_id}`"),
+            };
+        }
+        TokenChecker::shared(config).authorize(token, skill_id).await
+    }
 }
 
 #[cfg(test)]
@@ -232,12 +487,13 @@ mod tests {
     fn test_agent_new() {
         let skill = AgentSkill {
             id: "calculation".to_string(),
-            name: "Calculation".to_string(),
-            description: "Performs mathematical calculations".to_string(),
+            name: "Calculation".to_string().into(),
+            description: "Performs mathematical calculations".to_string().into(),
             tags: vec!["math".to_string(), "analysis".to_string()],
-            examples: Some(vec!["Calculate 2 + 2".to_string()]),
+            examples: Some(vec!["Calculate 2 + 2".to_string()].into()),
             input_modes: None,
             output_modes: None,
+            security: None,
         };
 
         let interface = AgentInterface {
@@ -269,12 +525,13 @@ mod tests {
     fn test_constellation_agent_new() {
         let skill = AgentSkill {
             id: "system-design".to_string(),
-            name: "System Design".to_string(),
-            description: "Designs system architecture".to_string(),
+            name: "System Design".to_string().into(),
+            description: "Designs system architecture".to_string().into(),
             tags: vec!["architecture".to_string(), "design".to_string()],
             examples: None,
             input_modes: None,
             output_modes: None,
+            security: None,
         };
 
         let interface = AgentInterface {
@@ -337,22 +594,24 @@ mod tests {
     fn test_skill_operations() {
         let skill1 = AgentSkill {
             id: "skill-1".to_string(),
-            name: "Skill One".to_string(),
-            description: "First skill".to_string(),
+            name: "Skill One".to_string().into(),
+            description: "First skill".to_string().into(),
             tags: vec!["tag1".to_string()],
             examples: None,
             input_modes: None,
             output_modes: None,
+            security: None,
         };
 
         let skill2 = AgentSkill {
             id: "skill-2".to_string(),
-            name: "Skill Two".to_string(),
-            description: "Second skill".to_string(),
+            name: "Skill Two".to_string().into(),
+            description: "Second skill".to_string().into(),
             tags: vec!["tag2".to_string()],
             examples: None,
             input_modes: None,
             output_modes: None,
+            security: None,
         };
 
         let agent = Agent::new(
@@ -376,12 +635,13 @@ mod tests {
     fn test_agent_serialization() {
         let skill = AgentSkill {
             id: "test-skill".to_string(),
-            name: "Test Skill".to_string(),
-            description: "A test skill".to_string(),
+            name: "Test Skill".to_string().into(),
+            description: "A test skill".to_string().into(),
             tags: vec!["test".to_string()],
-            examples: Some(vec!["Test example".to_string()]),
+            examples: Some(vec!["Test example".to_string()].into()),
             input_modes: Some(vec!["text/plain".to_string()]),
             output_modes: Some(vec!["application/json".to_string()]),
+            security: None,
         };
 
         let interface = AgentInterface {
@@ -393,7 +653,7 @@ mod tests {
         let agent = Agent {
             id: "test-agent".to_string(),
             name: "Test Agent".to_string(),
-            description: "A test agent".to_string(),
+            description: "A test agent".to_string().into(),
             protocol_version: "1.0".to_string(),
             version: "1.0.0".to_string(),
             default_input_modes: vec!["text/plain".to_string()],
@@ -423,6 +683,12 @@ mod tests {
             supports_extended_agent_card: Some(true),
             documentation_url: Some("https://docs.test.com".to_string()),
             icon_url: Some("https://test.com/icon.png".to_string()),
+            security_schemes: HashMap::new(),
+            security: Vec::new(),
+            supported_protocol_range: default_protocol_range(),
+            attestation: None,
+            authorization: None,
+            schema_version: super::migration::agent_schema_version(),
         };
 
         let json = serde_json::to_string(&agent).unwrap();
@@ -467,4 +733,151 @@ mod tests {
             assert_eq!(json, format!("\"{}\"", expected_str));
         }
     }
+
+    #[test]
+    fn test_negotiate_version_picks_highest_mutual() {
+        let mut a = Agent::new(
+            "a".to_string(),
+            "A".to_string(),
+            "desc".to_string(),
+            "P".to_string(),
+            vec![],
+            vec![],
+        );
+        a.protocol_version = "1.2".to_string();
+        let mut b = Agent::new(
+            "b".to_string(),
+            "B".to_string(),
+            "desc".to_string(),
+            "P".to_string(),
+            vec![],
+            vec![],
+        );
+        b.protocol_version = "1.4".to_string();
+
+        // Both default to the `1.x` range, so the higher 1.4 wins.
+        assert_eq!(
+            a.negotiate_version(&b),
+            Some(semver::Version::parse("1.4.0").unwrap())
+        );
+
+        // A peer stuck on 2.x shares no version within the default `1.x` range.
+        b.protocol_version = "2.0".to_string();
+        assert_eq!(a.negotiate_version(&b), None);
+    }
+
+    #[test]
+    fn test_negotiate_succeeds_for_two_default_agents_with_bare_protocol_versions() {
+        let a = Agent::new(
+            "a".to_string(),
+            "A".to_string(),
+            "desc".to_string(),
+            "P".to_string(),
+            vec![],
+            vec![],
+        );
+        let b = Agent::new(
+            "b".to_string(),
+            "B".to_string(),
+            "desc".to_string(),
+            "P".to_string(),
+            vec![],
+            vec![],
+        );
+
+        // Both default to the bare "1.0" protocol version; the peer's bare
+        // version must be padded the same way `self`'s is, or this fails
+        // with `NoCompatibleVersion` even though the two agents agree.
+        let peer = b.capability_negotiation();
+        let (version, _capabilities) = a.negotiate(&peer).expect("default agents should negotiate");
+        assert_eq!(version, semver::Version::parse("1.0.0").unwrap());
+    }
+
+    #[test]
+    fn test_required_schemes_merges_card_and_skill() {
+        use crate::models::security::{ApiKeyLocation, SecurityScheme};
+
+        let skill = AgentSkill {
+            id: "protected".to_string(),
+            name: "Protected".to_string().into(),
+            description: "Needs oauth".to_string().into(),
+            tags: vec![],
+            examples: None,
+            input_modes: None,
+            output_modes: None,
+            security: Some(vec![HashMap::from([(
+                "oauth".to_string(),
+                vec!["write".to_string()],
+            )])]),
+        };
+
+        let mut agent = Agent::new(
+            "a".to_string(),
+            "A".to_string(),
+            "desc".to_string(),
+            "Provider".to_string(),
+            vec![skill],
+            vec![],
+        );
+        agent.add_security_scheme(
+            "api".to_string(),
+            SecurityScheme::ApiKey {
+                name: "X-Key".to_string(),
+                location: ApiKeyLocation::Header,
+            },
+        );
+        agent.security = vec![HashMap::from([("api".to_string(), vec![])])];
+
+        // Skill-level requirements override the card-level default.
+        let required = agent.required_schemes_for_skill("protected").unwrap();
+        assert_eq!(required.len(), 1);
+        assert!(required[0].contains_key("oauth"));
+        assert!(agent.required_schemes_for_skill("missing").is_none());
+    }
+
+    #[test]
+    fn test_verify_attestation_fails_when_no_attestation_scheme_is_declared() {
+        use crate::models::remote_attestation::AttestationError;
+
+        let agent = Agent::new(
+            "a".to_string(),
+            "A".to_string(),
+            "desc".to_string(),
+            "Provider".to_string(),
+            vec![],
+            vec![],
+        );
+
+        assert!(matches!(
+            agent.resolve_attestation_target(),
+            Err(AttestationError::NoAttestationScheme)
+        ));
+    }
+
+    #[test]
+    fn test_verify_attestation_fails_when_card_carries_no_evidence() {
+        use crate::models::remote_attestation::AttestationError;
+        use crate::models::security::SecurityScheme;
+
+        let mut agent = Agent::new(
+            "a".to_string(),
+            "A".to_string(),
+            "desc".to_string(),
+            "Provider".to_string(),
+            vec![],
+            vec![],
+        );
+        agent.add_security_scheme(
+            "tee".to_string(),
+            SecurityScheme::Attestation {
+                attestation_endpoint: "https://attest.example/verify".to_string(),
+                expected_measurement: "abc123".to_string(),
+            },
+        );
+
+        assert!(matches!(
+            agent.resolve_attestation_target(),
+            Err(AttestationError::MissingEvidence)
+        ));
+    }
 }
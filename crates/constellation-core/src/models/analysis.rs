@@ -0,0 +1,249 @@
+//! Critical-path and bottleneck analysis over a [`DataTransformationGraph`].
+//!
+//! Once a graph has executed, every node carries timing
+//! (`completed_at - started_at`, falling back to `metrics.cpu_time_ms`). Treating
+//! the `data_flow` edges as a DAG, [`DataTransformationGraph::critical_path`]
+//! finds the longest-duration chain from a root to a sink — the sequence that
+//! bounds the graph's wall-clock time — and [`DataTransformationGraph::bottlenecks`]
+//! ranks the nodes on that path by their share of it, so users can see which
+//! agent/skill dominates a multi-agent run.
+
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+use super::dtg::DataTransformationGraph;
+
+/// The longest-duration chain of data-flow dependencies through a graph.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CriticalPath {
+    /// Nodes along the path, ordered from root to sink.
+    pub nodes: Vec<Uuid>,
+    /// Summed node duration along the path, in milliseconds.
+    pub total_ms: u64,
+}
+
+/// A node's contribution to the critical path.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bottleneck {
+    /// The node on the critical path.
+    pub node_id: Uuid,
+    /// Skill executed by the node.
+    pub skill_id: String,
+    /// Agent that executed the node.
+    pub agent_id: String,
+    /// Node duration in milliseconds.
+    pub duration_ms: u64,
+    /// Fraction of the critical path's total time spent in this node (0.0–1.0).
+    pub share: f64,
+}
+
+impl DataTransformationGraph {
+    /// Duration of a node in milliseconds: wall-clock when both timestamps are
+    /// present, otherwise the recorded CPU time.
+    fn node_duration_ms(&self, node_id: Uuid) -> u64 {
+        match self.nodes.get(&node_id) {
+            Some(node) => match node.completed_at {
+                Some(completed) => (completed - node.started_at)
+                    .num_milliseconds()
+                    .max(0) as u64,
+                None => node.metrics.cpu_time_ms,
+            },
+            None => 0,
+        }
+    }
+
+    /// Topological order over the `data_flow` edges (Kahn's algorithm). Returns
+    /// `None` if the graph is not acyclic.
+    fn data_flow_order(&self) -> Option<Vec<Uuid>> {
+        if !self.is_acyclic() {
+            return None;
+        }
+
+        let mut in_degree: HashMap<Uuid, usize> =
+            self.nodes.keys().map(|id| (*id, 0usize)).collect();
+        for edge in self.edges.iter().filter(|e| e.edge_type == "data_flow") {
+            if let Some(deg) = in_degree.get_mut(&edge.target) {
+                *deg += 1;
+            }
+        }
+
+        let mut queue: Vec<Uuid> = in_degree
+            .iter()
+            .filter(|(_, d)| **d == 0)
+            .map(|(id, _)| *id)
+            .collect();
+        let mut order = Vec::with_capacity(self.nodes.len());
+        while let Some(node_id) = queue.pop() {
+            order.push(node_id);
+            for edge in self
+                .edges
+                .iter()
+                .filter(|e| e.edge_type == "data_flow" && e.source == node_id)
+            {
+                if let Some(deg) = in_degree.get_mut(&edge.target) {
+                    *deg -= 1;
+                    if *deg == 0 {
+                        queue.push(edge.target);
+                    }
+                }
+            }
+        }
+        Some(order)
+    }
+
+    /// Compute the critical path: the longest-duration chain of `data_flow`
+    /// dependencies from a root to a sink. Requires the graph to be acyclic;
+    /// returns `None` for a cyclic or empty graph.
+    pub fn critical_path(&self) -> Option<CriticalPath> {
+        let order = self.data_flow_order()?;
+        if order.is_empty() {
+            return None;
+        }
+
+        // Longest-path DP: earliest_finish[n] = dur(n) + max over deps.
+        let mut earliest_finish: HashMap<Uuid, u64> = HashMap::new();
+        let mut predecessor: HashMap<Uuid, Option<Uuid>> = HashMap::new();
+
+        for node_id in &order {
+            let mut best_dep_finish = 0u64;
+            let mut best_pred = None;
+            for edge in self
+                .edges
+                .iter()
+                .filter(|e| e.edge_type == "data_flow" && e.target == *node_id)
+            {
+                let dep_finish = *earliest_finish.get(&edge.source).unwrap_or(&0);
+                if dep_finish >= best_dep_finish {
+                    best_dep_finish = dep_finish;
+                    best_pred = Some(edge.source);
+                }
+            }
+            earliest_finish.insert(*node_id, self.node_duration_ms(*node_id) + best_dep_finish);
+            predecessor.insert(*node_id, best_pred);
+        }
+
+        let (sink, total_ms) = earliest_finish
+            .iter()
+            .max_by_key(|(_, finish)| **finish)
+            .map(|(id, finish)| (*id, *finish))?;
+
+        let mut nodes = Vec::new();
+        let mut current = Some(sink);
+        while let Some(node_id) = current {
+            nodes.push(node_id);
+            current = predecessor.get(&node_id).copied().flatten();
+        }
+        nodes.reverse();
+
+        Some(CriticalPath { nodes, total_ms })
+    }
+
+    /// Rank the critical-path nodes by their share of its total time, highest
+    /// first, so the dominant agent/skill surfaces at the top.
+    pub fn bottlenecks(&self) -> Vec<Bottleneck> {
+        let Some(path) = self.critical_path() else {
+            return Vec::new();
+        };
+        let total = path.total_ms.max(1) as f64;
+
+        let mut bottlenecks: Vec<Bottleneck> = path
+            .nodes
+            .iter()
+            .filter_map(|id| {
+                let node = self.nodes.get(id)?;
+                let duration_ms = self.node_duration_ms(*id);
+                Some(Bottleneck {
+                    node_id: *id,
+                    skill_id: node.skill_id.clone(),
+                    agent_id: node.agent_id.clone(),
+                    duration_ms,
+                    share: duration_ms as f64 / total,
+                })
+            })
+            .collect();
+        bottlenecks.sort_by(|a, b| b.duration_ms.cmp(&a.duration_ms));
+        bottlenecks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::dtg::DtgNode;
+
+    /// Add a node to `graph` with `duration_ms` recorded via `metrics.cpu_time_ms`
+    /// (no `completed_at`, so `node_duration_ms` falls back to it deterministically).
+    fn node_with_duration(graph: &mut DataTransformationGraph, duration_ms: u64) -> Uuid {
+        let mut node = DtgNode::new("skill".to_string(), "agent".to_string());
+        node.metrics.cpu_time_ms = duration_ms;
+        graph.add_node(node)
+    }
+
+    #[test]
+    fn test_critical_path_is_none_for_an_empty_graph() {
+        let graph = DataTransformationGraph::new("empty".to_string());
+        assert!(graph.critical_path().is_none());
+    }
+
+    #[test]
+    fn test_critical_path_is_none_for_a_cyclic_graph() {
+        let mut graph = DataTransformationGraph::new("cyclic".to_string());
+        let a = node_with_duration(&mut graph, 10);
+        let b = node_with_duration(&mut graph, 10);
+        graph.add_edge(a, b, Uuid::new_v4(), "data_flow".to_string());
+        graph.add_edge(b, a, Uuid::new_v4(), "data_flow".to_string());
+
+        assert!(graph.critical_path().is_none());
+    }
+
+    #[test]
+    fn test_critical_path_picks_the_longest_duration_chain() {
+        let mut graph = DataTransformationGraph::new("branching".to_string());
+        let a = node_with_duration(&mut graph, 10);
+        let b = node_with_duration(&mut graph, 50);
+        let c = node_with_duration(&mut graph, 5);
+        graph.add_edge(a, b, Uuid::new_v4(), "data_flow".to_string());
+        graph.add_edge(a, c, Uuid::new_v4(), "data_flow".to_string());
+
+        let path = graph.critical_path().unwrap();
+        assert_eq!(path.nodes, vec![a, b]);
+        assert_eq!(path.total_ms, 60);
+    }
+
+    #[test]
+    fn test_critical_path_ignores_non_data_flow_edges() {
+        let mut graph = DataTransformationGraph::new("weak-only".to_string());
+        let a = node_with_duration(&mut graph, 10);
+        let b = node_with_duration(&mut graph, 50);
+        graph.add_weak_edge(a, b, Uuid::new_v4());
+
+        let path = graph.critical_path().unwrap();
+        // With no `data_flow` edge between them, each node is its own
+        // singleton chain; the longer one alone is the critical path.
+        assert_eq!(path.nodes, vec![b]);
+        assert_eq!(path.total_ms, 50);
+    }
+
+    #[test]
+    fn test_bottlenecks_ranks_critical_path_nodes_by_duration_descending() {
+        let mut graph = DataTransformationGraph::new("branching".to_string());
+        let a = node_with_duration(&mut graph, 10);
+        let b = node_with_duration(&mut graph, 50);
+        graph.add_edge(a, b, Uuid::new_v4(), "data_flow".to_string());
+
+        let bottlenecks = graph.bottlenecks();
+        assert_eq!(bottlenecks.len(), 2);
+        assert_eq!(bottlenecks[0].node_id, b);
+        assert_eq!(bottlenecks[0].duration_ms, 50);
+        assert!((bottlenecks[0].share - 50.0 / 60.0).abs() < f64::EPSILON);
+        assert_eq!(bottlenecks[1].node_id, a);
+        assert!((bottlenecks[1].share - 10.0 / 60.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_bottlenecks_is_empty_for_a_graph_with_no_critical_path() {
+        let graph = DataTransformationGraph::new("empty".to_string());
+        assert!(graph.bottlenecks().is_empty());
+    }
+}
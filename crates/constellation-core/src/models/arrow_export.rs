@@ -0,0 +1,561 @@
+//! Apache Arrow columnar export for [`DataTransformationGraph`].
+//!
+//! Per-graph pretty-JSON is the wrong shape for analytics that span many
+//! executed graphs. This module flattens a DTG — or a batch of them — into two
+//! columnar [`RecordBatch`]es: a *nodes* table (one row per [`DtgNode`], carrying
+//! its metrics) and an *edges* table (one row per [`DtgEdge`], resolving the
+//! referenced [`DtgDataRef`]). Downstream tooling can then run predicate
+//! pushdown on, e.g., `agent_id` or a quality threshold instead of parsing JSON.
+//!
+//! [`DataTransformationGraph::to_record_batches`] does the in-process conversion;
+//! [`graphs_to_record_batches`] does the same over a slice. When the `arrow-flight`
+//! feature is enabled, [`DtgFlightService`] serves the two tables over Arrow Flight.
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::sync::Arc;
+
+use arrow::array::{
+    ArrayRef, Float64Array, StringArray, TimestampMicrosecondArray, UInt32Array, UInt64Array,
+};
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef, TimeUnit};
+use arrow::error::ArrowError;
+use arrow::record_batch::RecordBatch;
+use uuid::Uuid;
+
+use super::dtg::{DataTransformationGraph, DtgDataRef, DtgNodeStatus};
+
+/// The UTC timezone marker applied to Arrow timestamp columns.
+const TZ: &str = "UTC";
+
+/// The two columnar tables produced from one or more DTGs.
+#[derive(Debug, Clone)]
+pub struct DtgRecordBatches {
+    /// One row per DTG node, with its metrics flattened out.
+    pub nodes: RecordBatch,
+    /// One row per DTG edge, with the referenced data ref resolved.
+    pub edges: RecordBatch,
+}
+
+/// Schema of the *nodes* table.
+pub fn nodes_schema() -> SchemaRef {
+    let ts = DataType::Timestamp(TimeUnit::Microsecond, Some(TZ.into()));
+    Arc::new(Schema::new(vec![
+        Field::new("graph_id", DataType::Utf8, false),
+        Field::new("node_id", DataType::Utf8, false),
+        Field::new("skill_id", DataType::Utf8, false),
+        Field::new("agent_id", DataType::Utf8, false),
+        Field::new("status", DataType::Utf8, false),
+        Field::new("cpu_time_ms", DataType::UInt64, false),
+        Field::new("memory_bytes", DataType::UInt64, false),
+        Field::new("network_bytes", DataType::UInt64, false),
+        Field::new("disk_bytes", DataType::UInt64, false),
+        Field::new("retry_count", DataType::UInt32, false),
+        Field::new("quality_score", DataType::Float64, false),
+        Field::new("confidence_score", DataType::Float64, false),
+        Field::new("started_at", ts.clone(), false),
+        Field::new("completed_at", ts, true),
+    ]))
+}
+
+/// Schema of the *edges* table.
+pub fn edges_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("from_node", DataType::Utf8, false),
+        Field::new("to_node", DataType::Utf8, false),
+        Field::new("data_ref_id", DataType::Utf8, false),
+        Field::new("data_type", DataType::Utf8, true),
+        Field::new("size_bytes", DataType::UInt64, true),
+        Field::new("content_hash", DataType::Utf8, true),
+    ]))
+}
+
+/// The wire name for the nodes table, used as the Flight ticket / descriptor.
+pub const NODES_TABLE: &str = "dtg_nodes";
+/// The wire name for the edges table, used as the Flight ticket / descriptor.
+pub const EDGES_TABLE: &str = "dtg_edges";
+
+fn status_str(status: &DtgNodeStatus) -> &'static str {
+    match status {
+        DtgNodeStatus::Pending => "pending",
+        DtgNodeStatus::Executing => "executing",
+        DtgNodeStatus::Completed => "completed",
+        DtgNodeStatus::Failed => "failed",
+        DtgNodeStatus::Cancelled => "cancelled",
+        DtgNodeStatus::Waiting => "waiting",
+    }
+}
+
+impl DataTransformationGraph {
+    /// Flatten this graph into the columnar nodes and edges tables.
+    pub fn to_record_batches(&self) -> Result<DtgRecordBatches, ArrowError> {
+        graphs_to_record_batches(std::slice::from_ref(self))
+    }
+}
+
+/// Collect every data ref reachable from a graph, keyed by id, so edges can
+/// resolve the `data_type`/`size_bytes`/`content_hash` of the ref they carry.
+fn data_ref_index(graph: &DataTransformationGraph) -> HashMap<Uuid, &DtgDataRef> {
+    let mut index = HashMap::new();
+    let node_refs = graph
+        .nodes
+        .values()
+        .flat_map(|node| node.inputs.iter().chain(node.outputs.iter()));
+    let refs = node_refs
+        .chain(graph.graph_inputs.iter())
+        .chain(graph.graph_outputs.iter());
+    for r in refs {
+        index.entry(r.id).or_insert(r);
+    }
+    index
+}
+
+/// Flatten a batch of graphs into two [`RecordBatch`]es sharing one schema each.
+pub fn graphs_to_record_batches(
+    graphs: &[DataTransformationGraph],
+) -> Result<DtgRecordBatches, ArrowError> {
+    let mut graph_id = Vec::new();
+    let mut node_id = Vec::new();
+    let mut skill_id = Vec::new();
+    let mut agent_id = Vec::new();
+    let mut status = Vec::new();
+    let mut cpu_time_ms = Vec::new();
+    let mut memory_bytes = Vec::new();
+    let mut network_bytes = Vec::new();
+    let mut disk_bytes = Vec::new();
+    let mut retry_count = Vec::new();
+    let mut quality_score = Vec::new();
+    let mut confidence_score = Vec::new();
+    let mut started_at = Vec::new();
+    let mut completed_at = Vec::new();
+
+    let mut from_node = Vec::new();
+    let mut to_node = Vec::new();
+    let mut data_ref_id = Vec::new();
+    let mut data_type = Vec::new();
+    let mut size_bytes = Vec::new();
+    let mut content_hash = Vec::new();
+
+    for graph in graphs {
+        let gid = graph.id.to_string();
+        for node in graph.nodes.values() {
+            graph_id.push(gid.clone());
+            node_id.push(node.id.to_string());
+            skill_id.push(node.skill_id.clone());
+            agent_id.push(node.agent_id.clone());
+            status.push(status_str(&node.status).to_string());
+            cpu_time_ms.push(node.metrics.cpu_time_ms);
+            memory_bytes.push(node.metrics.memory_bytes);
+            network_bytes.push(node.metrics.network_bytes);
+            disk_bytes.push(node.metrics.disk_bytes);
+            retry_count.push(node.metrics.retry_count);
+            quality_score.push(node.metrics.quality_score);
+            confidence_score.push(node.metrics.confidence_score);
+            started_at.push(node.started_at.timestamp_micros());
+            completed_at.push(node.completed_at.map(|t| t.timestamp_micros()));
+        }
+
+        let index = data_ref_index(graph);
+        for edge in &graph.edges {
+            from_node.push(edge.source.to_string());
+            to_node.push(edge.target.to_string());
+            data_ref_id.push(edge.data_ref.to_string());
+            let resolved = index.get(&edge.data_ref);
+            data_type.push(resolved.map(|r| r.data_type.clone()));
+            size_bytes.push(resolved.and_then(|r| r.size_bytes));
+            content_hash.push(resolved.and_then(|r| r.content_hash.clone()));
+        }
+    }
+
+    let nodes = RecordBatch::try_new(
+        nodes_schema(),
+        vec![
+            Arc::new(StringArray::from(graph_id)) as ArrayRef,
+            Arc::new(StringArray::from(node_id)),
+            Arc::new(StringArray::from(skill_id)),
+            Arc::new(StringArray::from(agent_id)),
+            Arc::new(StringArray::from(status)),
+            Arc::new(UInt64Array::from(cpu_time_ms)),
+            Arc::new(UInt64Array::from(memory_bytes)),
+            Arc::new(UInt64Array::from(network_bytes)),
+            Arc::new(UInt64Array::from(disk_bytes)),
+            Arc::new(UInt32Array::from(retry_count)),
+            Arc::new(Float64Array::from(quality_score)),
+            Arc::new(Float64Array::from(confidence_score)),
+            Arc::new(TimestampMicrosecondArray::from(started_at).with_timezone(TZ)),
+            Arc::new(TimestampMicrosecondArray::from(completed_at).with_timezone(TZ)),
+        ],
+    )?;
+
+    let edges = RecordBatch::try_new(
+        edges_schema(),
+        vec![
+            Arc::new(StringArray::from(from_node)) as ArrayRef,
+            Arc::new(StringArray::from(to_node)),
+            Arc::new(StringArray::from(data_ref_id)),
+            Arc::new(StringArray::from(data_type)),
+            Arc::new(UInt64Array::from(size_bytes)),
+            Arc::new(StringArray::from(content_hash)),
+        ],
+    )?;
+
+    Ok(DtgRecordBatches { nodes, edges })
+}
+
+/// A streaming writer that appends many graphs to a pair of Arrow IPC streams,
+/// one for nodes and one for edges, so thousands of graphs can be persisted in
+/// columnar form without holding them all in memory.
+///
+/// Each [`append`](DtgArrowWriter::append) converts one graph and writes a
+/// record batch to each stream; [`finish`](DtgArrowWriter::finish) closes both.
+/// The resulting files are readable by any Arrow IPC reader and, via the
+/// `parquet` feature, can be transcoded to Parquet for columnar query engines.
+pub struct DtgArrowWriter<W: Write> {
+    nodes: arrow::ipc::writer::StreamWriter<W>,
+    edges: arrow::ipc::writer::StreamWriter<W>,
+}
+
+impl<W: Write> DtgArrowWriter<W> {
+    /// Open IPC streams over `nodes_out` and `edges_out`.
+    pub fn new(nodes_out: W, edges_out: W) -> Result<Self, ArrowError> {
+        Ok(Self {
+            nodes: arrow::ipc::writer::StreamWriter::try_new(nodes_out, &nodes_schema())?,
+            edges: arrow::ipc::writer::StreamWriter::try_new(edges_out, &edges_schema())?,
+        })
+    }
+
+    /// Flatten `graph` and append one batch to each stream.
+    pub fn append(&mut self, graph: &DataTransformationGraph) -> Result<(), ArrowError> {
+        let batches = graph.to_record_batches()?;
+        self.nodes.write(&batches.nodes)?;
+        self.edges.write(&batches.edges)?;
+        Ok(())
+    }
+
+    /// Flush and close both streams, writing the IPC end-of-stream markers.
+    pub fn finish(mut self) -> Result<(), ArrowError> {
+        self.nodes.finish()?;
+        self.edges.finish()?;
+        Ok(())
+    }
+}
+
+/// Write the flattened nodes table for a batch of graphs to a Parquet writer,
+/// for querying with columnar engines (DuckDB, DataFusion, Spark, …).
+#[cfg(feature = "parquet")]
+pub fn write_nodes_parquet<W: Write + Send>(
+    graphs: &[DataTransformationGraph],
+    out: W,
+) -> Result<(), ArrowError> {
+    use parquet::arrow::ArrowWriter;
+
+    let batches = graphs_to_record_batches(graphs)?;
+    let mut writer = ArrowWriter::try_new(out, nodes_schema(), None)
+        .map_err(|e| ArrowError::ExternalError(Box::new(e)))?;
+    writer
+        .write(&batches.nodes)
+        .map_err(|e| ArrowError::ExternalError(Box::new(e)))?;
+    writer
+        .close()
+        .map_err(|e| ArrowError::ExternalError(Box::new(e)))?;
+    Ok(())
+}
+
+/// An Arrow Flight service that serves the DTG nodes and edges tables.
+///
+/// Clients list the two flights (`dtg_nodes`, `dtg_edges`) and pull either with
+/// a `DoGet`; downstream engines apply their own predicate pushdown on the
+/// returned columns (e.g. `agent_id = ...` or `quality_score >= 0.9`).
+#[cfg(feature = "arrow-flight")]
+pub struct DtgFlightService {
+    batches: DtgRecordBatches,
+}
+
+#[cfg(feature = "arrow-flight")]
+impl DtgFlightService {
+    /// Build a service over a precomputed batch of graphs.
+    pub fn new(graphs: &[DataTransformationGraph]) -> Result<Self, ArrowError> {
+        Ok(Self {
+            batches: graphs_to_record_batches(graphs)?,
+        })
+    }
+
+    fn table(&self, name: &str) -> Option<(&RecordBatch, SchemaRef)> {
+        match name {
+            NODES_TABLE => Some((&self.batches.nodes, nodes_schema())),
+            EDGES_TABLE => Some((&self.batches.edges, edges_schema())),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "arrow-flight")]
+#[tonic::async_trait]
+impl arrow_flight::flight_service_server::FlightService for DtgFlightService {
+    type HandshakeStream = futures::stream::BoxStream<
+        'static,
+        Result<arrow_flight::HandshakeResponse, tonic::Status>,
+    >;
+    type ListFlightsStream =
+        futures::stream::BoxStream<'static, Result<arrow_flight::FlightInfo, tonic::Status>>;
+    type DoGetStream =
+        futures::stream::BoxStream<'static, Result<arrow_flight::FlightData, tonic::Status>>;
+    type DoPutStream =
+        futures::stream::BoxStream<'static, Result<arrow_flight::PutResult, tonic::Status>>;
+    type DoExchangeStream =
+        futures::stream::BoxStream<'static, Result<arrow_flight::FlightData, tonic::Status>>;
+    type DoActionStream =
+        futures::stream::BoxStream<'static, Result<arrow_flight::Result, tonic::Status>>;
+    type ListActionsStream =
+        futures::stream::BoxStream<'static, Result<arrow_flight::ActionType, tonic::Status>>;
+
+    async fn get_flight_info(
+        &self,
+        request: tonic::Request<arrow_flight::FlightDescriptor>,
+    ) -> Result<tonic::Response<arrow_flight::FlightInfo>, tonic::Status> {
+        use arrow_flight::{FlightEndpoint, FlightInfo, Ticket};
+
+        let descriptor = request.into_inner();
+        let name = descriptor
+            .path
+            .first()
+            .cloned()
+            .unwrap_or_default();
+        let (batch, schema) = self
+            .table(&name)
+            .ok_or_else(|| tonic::Status::not_found(format!("unknown table: {name}")))?;
+
+        let info = FlightInfo::new()
+            .try_with_schema(&schema)
+            .map_err(|e| tonic::Status::internal(e.to_string()))?
+            .with_endpoint(FlightEndpoint::new().with_ticket(Ticket::new(name.into_bytes())))
+            .with_descriptor(descriptor)
+            .with_total_records(batch.num_rows() as i64);
+        Ok(tonic::Response::new(info))
+    }
+
+    async fn do_get(
+        &self,
+        request: tonic::Request<arrow_flight::Ticket>,
+    ) -> Result<tonic::Response<Self::DoGetStream>, tonic::Status> {
+        use arrow_flight::encode::FlightDataEncoderBuilder;
+        use futures::StreamExt;
+
+        let ticket = request.into_inner();
+        let name = String::from_utf8_lossy(&ticket.ticket).to_string();
+        let (batch, schema) = self
+            .table(&name)
+            .ok_or_else(|| tonic::Status::not_found(format!("unknown ticket: {name}")))?;
+
+        let batch = batch.clone();
+        let stream = FlightDataEncoderBuilder::new()
+            .with_schema(schema)
+            .build(futures::stream::iter(vec![Ok(batch)]))
+            .map(|r| r.map_err(|e| tonic::Status::internal(e.to_string())));
+        Ok(tonic::Response::new(stream.boxed()))
+    }
+
+    async fn handshake(
+        &self,
+        _request: tonic::Request<tonic::Streaming<arrow_flight::HandshakeRequest>>,
+    ) -> Result<tonic::Response<Self::HandshakeStream>, tonic::Status> {
+        Err(tonic::Status::unimplemented("handshake"))
+    }
+
+    async fn list_flights(
+        &self,
+        _request: tonic::Request<arrow_flight::Criteria>,
+    ) -> Result<tonic::Response<Self::ListFlightsStream>, tonic::Status> {
+        Err(tonic::Status::unimplemented("list_flights"))
+    }
+
+    async fn do_put(
+        &self,
+        _request: tonic::Request<tonic::Streaming<arrow_flight::FlightData>>,
+    ) -> Result<tonic::Response<Self::DoPutStream>, tonic::Status> {
+        Err(tonic::Status::unimplemented("do_put"))
+    }
+
+    async fn do_exchange(
+        &self,
+        _request: tonic::Request<tonic::Streaming<arrow_flight::FlightData>>,
+    ) -> Result<tonic::Response<Self::DoExchangeStream>, tonic::Status> {
+        Err(tonic::Status::unimplemented("do_exchange"))
+    }
+
+    async fn do_action(
+        &self,
+        _request: tonic::Request<arrow_flight::Action>,
+    ) -> Result<tonic::Response<Self::DoActionStream>, tonic::Status> {
+        Err(tonic::Status::unimplemented("do_action"))
+    }
+
+    async fn list_actions(
+        &self,
+        _request: tonic::Request<arrow_flight::Empty>,
+    ) -> Result<tonic::Response<Self::ListActionsStream>, tonic::Status> {
+        Err(tonic::Status::unimplemented("list_actions"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::dtg::DtgNode;
+
+    fn graph_with_one_edge() -> DataTransformationGraph {
+        let mut graph = DataTransformationGraph::new("test".to_string());
+        let mut producer = DtgNode::new("produce".to_string(), "agent-a".to_string());
+        let output = DtgDataRef {
+            id: Uuid::new_v4(),
+            data_type: "json".to_string(),
+            schema: None,
+            size_bytes: Some(128),
+            content_hash: Some("deadbeef".to_string()),
+            storage_ref: None,
+        };
+        producer.add_output(output.clone());
+        producer.mark_completed(crate::models::dtg::DtgMetrics {
+            cpu_time_ms: 10,
+            memory_bytes: 0,
+            network_bytes: 0,
+            disk_bytes: 0,
+            retry_count: 0,
+            quality_score: 1.0,
+            confidence_score: 1.0,
+        });
+        let producer_id = graph.add_node(producer);
+
+        let mut consumer = DtgNode::new("consume".to_string(), "agent-b".to_string());
+        consumer.add_input(output.clone());
+        let consumer_id = graph.add_node(consumer);
+
+        graph.add_edge(producer_id, consumer_id, output.id, "data_flow".to_string());
+        graph
+    }
+
+    fn string_column<'a>(batch: &'a RecordBatch, name: &str) -> &'a StringArray {
+        batch
+            .column(batch.schema().index_of(name).unwrap())
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_to_record_batches_produces_one_row_per_node_and_edge() {
+        let graph = graph_with_one_edge();
+        let batches = graph.to_record_batches().unwrap();
+
+        assert_eq!(batches.nodes.num_rows(), 2);
+        assert_eq!(batches.edges.num_rows(), 1);
+    }
+
+    #[test]
+    fn test_nodes_table_carries_status_and_metrics() {
+        let graph = graph_with_one_edge();
+        let batches = graph.to_record_batches().unwrap();
+
+        let agent_ids: Vec<&str> = string_column(&batches.nodes, "agent_id").iter().map(|v| v.unwrap()).collect();
+        assert!(agent_ids.contains(&"agent-a"));
+        assert!(agent_ids.contains(&"agent-b"));
+
+        let status: Vec<&str> = string_column(&batches.nodes, "status").iter().map(|v| v.unwrap()).collect();
+        assert!(status.contains(&"completed"));
+        assert!(status.contains(&"pending"));
+
+        let cpu_time_ms = batches
+            .nodes
+            .column(batches.nodes.schema().index_of("cpu_time_ms").unwrap())
+            .as_any()
+            .downcast_ref::<UInt64Array>()
+            .unwrap();
+        assert!(cpu_time_ms.iter().any(|v| v == Some(10)));
+    }
+
+    #[test]
+    fn test_edges_table_resolves_the_referenced_data_refs_metadata() {
+        let graph = graph_with_one_edge();
+        let batches = graph.to_record_batches().unwrap();
+
+        let data_type = string_column(&batches.edges, "data_type");
+        assert_eq!(data_type.value(0), "json");
+        let content_hash = string_column(&batches.edges, "content_hash");
+        assert_eq!(content_hash.value(0), "deadbeef");
+
+        let size_bytes = batches
+            .edges
+            .column(batches.edges.schema().index_of("size_bytes").unwrap())
+            .as_any()
+            .downcast_ref::<UInt64Array>()
+            .unwrap();
+        assert_eq!(size_bytes.value(0), 128);
+    }
+
+    #[test]
+    fn test_graphs_to_record_batches_concatenates_multiple_graphs() {
+        let a = graph_with_one_edge();
+        let b = graph_with_one_edge();
+        let batches = graphs_to_record_batches(&[a, b]).unwrap();
+
+        assert_eq!(batches.nodes.num_rows(), 4);
+        assert_eq!(batches.edges.num_rows(), 2);
+    }
+
+    #[test]
+    fn test_status_str_covers_every_node_status_variant() {
+        assert_eq!(status_str(&DtgNodeStatus::Pending), "pending");
+        assert_eq!(status_str(&DtgNodeStatus::Executing), "executing");
+        assert_eq!(status_str(&DtgNodeStatus::Completed), "completed");
+        assert_eq!(status_str(&DtgNodeStatus::Failed), "failed");
+        assert_eq!(status_str(&DtgNodeStatus::Cancelled), "cancelled");
+        assert_eq!(status_str(&DtgNodeStatus::Waiting), "waiting");
+    }
+
+    #[test]
+    fn test_dtg_arrow_writer_round_trips_appended_graphs_through_ipc_streams() {
+        let a = graph_with_one_edge();
+        let b = graph_with_one_edge();
+
+        let mut nodes_out = Vec::new();
+        let mut edges_out = Vec::new();
+        {
+            let mut writer = DtgArrowWriter::new(&mut nodes_out, &mut edges_out).unwrap();
+            writer.append(&a).unwrap();
+            writer.append(&b).unwrap();
+            writer.finish().unwrap();
+        }
+
+        let mut nodes_reader =
+            arrow::ipc::reader::StreamReader::try_new(nodes_out.as_slice(), None).unwrap();
+        let node_batches: Vec<RecordBatch> = nodes_reader.by_ref().map(|b| b.unwrap()).collect();
+        let total_node_rows: usize = node_batches.iter().map(|b| b.num_rows()).sum();
+        assert_eq!(total_node_rows, 4);
+        assert_eq!(nodes_reader.schema(), nodes_schema());
+
+        let mut edges_reader =
+            arrow::ipc::reader::StreamReader::try_new(edges_out.as_slice(), None).unwrap();
+        let edge_batches: Vec<RecordBatch> = edges_reader.by_ref().map(|b| b.unwrap()).collect();
+        let total_edge_rows: usize = edge_batches.iter().map(|b| b.num_rows()).sum();
+        assert_eq!(total_edge_rows, 2);
+        assert_eq!(edges_reader.schema(), edges_schema());
+    }
+
+    #[test]
+    fn test_dtg_arrow_writer_finish_with_no_appends_still_writes_valid_empty_streams() {
+        let mut nodes_out = Vec::new();
+        let mut edges_out = Vec::new();
+        {
+            let writer = DtgArrowWriter::new(&mut nodes_out, &mut edges_out).unwrap();
+            writer.finish().unwrap();
+        }
+
+        let mut nodes_reader =
+            arrow::ipc::reader::StreamReader::try_new(nodes_out.as_slice(), None).unwrap();
+        assert!(nodes_reader.next().is_none());
+
+        let mut edges_reader =
+            arrow::ipc::reader::StreamReader::try_new(edges_out.as_slice(), None).unwrap();
+        assert!(edges_reader.next().is_none());
+    }
+}
@@ -0,0 +1,355 @@
+//! Token-based authorization for A2A [`AgentInterface`](super::agent::AgentInterface)
+//! endpoints.
+//!
+//! An AgentCard publishes public URLs but carries no notion of who may call
+//! each skill. [`JwtAuthConfig`] declares an OIDC-style bearer scheme — a JWKS
+//! URI, an optional userinfo endpoint, the JWT claims a caller must present, and
+//! an optional policy reference — and serializes into the card so callers can
+//! discover it. At runtime a [`TokenChecker`] validates bearer tokens against
+//! the (cached) JWKS, enforces the required claims, and gates access per
+//! [`AgentSkill::id`](super::agent::AgentSkill::id), surfaced through
+//! [`Agent::authorize`](super::agent::Agent::authorize).
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+/// A2A-compliant JWT bearer authorization declaration for an agent card.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct JwtAuthConfig {
+    /// JWKS URI used to fetch the signing keys that validate bearer tokens.
+    pub jwks_uri: String,
+    /// Optional userinfo endpoint for resolving additional caller claims.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub userinfo_endpoint: Option<String>,
+    /// Claims every caller must present, as exact claim-name → value matches.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub required_claims: HashMap<String, serde_json::Value>,
+    /// Additional required claims gated per skill id.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub skill_claims: HashMap<String, HashMap<String, serde_json::Value>>,
+    /// Optional reference to an external authorization policy document.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub policy_ref: Option<String>,
+}
+
+/// The outcome of an authorization check.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuthzDecision {
+    /// Whether the call is permitted.
+    pub allowed: bool,
+    /// Human-readable reason for the decision.
+    pub reason: String,
+}
+
+impl AuthzDecision {
+    fn allow() -> Self {
+        Self {
+            allowed: true,
+            reason: "token valid and required claims satisfied".to_string(),
+        }
+    }
+
+    fn deny(reason: impl Into<String>) -> Self {
+        Self {
+            allowed: false,
+            reason: reason.into(),
+        }
+    }
+}
+
+/// Error raised while validating a bearer token.
+#[derive(Debug)]
+pub enum AuthzError {
+    /// The JWKS could not be fetched or parsed.
+    Jwks(String),
+    /// The token signature or structure was invalid.
+    Token(String),
+}
+
+impl std::fmt::Display for AuthzError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AuthzError::Jwks(msg) => write!(f, "JWKS resolution failed: {msg}"),
+            AuthzError::Token(msg) => write!(f, "token validation failed: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for AuthzError {}
+
+struct CachedJwks {
+    keys: jsonwebtoken::jwk::JwkSet,
+    fetched_at: Instant,
+}
+
+/// Validates bearer tokens against a cached JWKS and enforces claim policy.
+pub struct TokenChecker {
+    config: JwtAuthConfig,
+    http: reqwest::Client,
+    cache: Mutex<Option<CachedJwks>>,
+    cache_ttl: Duration,
+}
+
+impl TokenChecker {
+    /// Default JWKS cache lifetime.
+    const DEFAULT_TTL: Duration = Duration::from_secs(300);
+
+    /// Create a checker for `config` with the default cache expiry.
+    pub fn new(config: JwtAuthConfig) -> Self {
+        Self {
+            config,
+            http: reqwest::Client::new(),
+            cache: Mutex::new(None),
+            cache_ttl: Self::DEFAULT_TTL,
+        }
+    }
+
+    /// Override the JWKS cache lifetime.
+    pub fn with_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.cache_ttl = ttl;
+        self
+    }
+
+    /// Return the process-wide [`TokenChecker`] for `config`'s `jwks_uri`,
+    /// building and caching one on first use. Reusing one instance per JWKS
+    /// endpoint is what makes the JWKS cache actually apply across calls,
+    /// instead of every caller paying a fresh fetch.
+    pub fn shared(config: &JwtAuthConfig) -> Arc<TokenChecker> {
+        static CHECKERS: OnceLock<Mutex<HashMap<String, Arc<TokenChecker>>>> = OnceLock::new();
+        let checkers = CHECKERS.get_or_init(|| Mutex::new(HashMap::new()));
+        let mut checkers = checkers.lock().expect("token checker registry mutex poisoned");
+        checkers
+            .entry(config.jwks_uri.clone())
+            .or_insert_with(|| Arc::new(TokenChecker::new(config.clone())))
+            .clone()
+    }
+
+    /// Fetch the JWKS, serving a cached copy while it is still fresh.
+    async fn jwks(&self) -> Result<jsonwebtoken::jwk::JwkSet, AuthzError> {
+        {
+            let cache = self.cache.lock().expect("jwks cache mutex poisoned");
+            if let Some(cached) = cache.as_ref() {
+                if cached.fetched_at.elapsed() < self.cache_ttl {
+                    return Ok(cached.keys.clone());
+                }
+            }
+        }
+        let body = self
+            .http
+            .get(&self.config.jwks_uri)
+            .send()
+            .await
+            .and_then(|resp| resp.error_for_status())
+            .map_err(|e| AuthzError::Jwks(e.to_string()))?
+            .text()
+            .await
+            .map_err(|e| AuthzError::Jwks(e.to_string()))?;
+        let keys: jsonwebtoken::jwk::JwkSet =
+            serde_json::from_str(&body).map_err(|e| AuthzError::Jwks(e.to_string()))?;
+        let mut cache = self.cache.lock().expect("jwks cache mutex poisoned");
+        *cache = Some(CachedJwks {
+            keys: keys.clone(),
+            fetched_at: Instant::now(),
+        });
+        Ok(keys)
+    }
+
+    /// Validate `token`'s signature against the JWKS and return its claims.
+    pub async fn validate(&self, token: &str) -> Result<serde_json::Value, AuthzError> {
+        let jwks = self.jwks().await?;
+        decode_claims(token, &jwks)
+    }
+
+    /// Authorize a bearer token for `skill_id`, enforcing the global and
+    /// per-skill required claims.
+    pub async fn authorize(&self, token: &str, skill_id: &str) -> AuthzDecision {
+        let claims = match self.validate(token).await {
+            Ok(claims) => claims,
+            Err(e) => return AuthzDecision::deny(e.to_string()),
+        };
+        if let Some(missing) = first_unmet_claim(&self.config.required_claims, &claims) {
+            return AuthzDecision::deny(format!("missing or mismatched claim `{missing}`"));
+        }
+        if let Some(skill_required) = self.config.skill_claims.get(skill_id) {
+            if let Some(missing) = first_unmet_claim(skill_required, &claims) {
+                return AuthzDecision::deny(format!(
+                    "missing or mismatched claim `{missing}` for skill `{skill_id}`"
+                ));
+            }
+        }
+        AuthzDecision::allow()
+    }
+}
+
+/// Return the first required claim absent from or unequal to `claims`.
+fn first_unmet_claim<'a>(
+    required: &'a HashMap<String, serde_json::Value>,
+    claims: &serde_json::Value,
+) -> Option<&'a str> {
+    required
+        .iter()
+        .find(|(name, expected)| claims.get(name.as_str()) != Some(expected))
+        .map(|(name, _)| name.as_str())
+}
+
+/// Decode and signature-verify `token` against `jwks`, returning its claims.
+/// Audience validation is left to [`TokenChecker::authorize`]'s generic
+/// `required_claims`/`skill_claims` matching, since `JwtAuthConfig` expresses
+/// "aud" as an ordinary required claim rather than a dedicated field;
+/// expiry is still enforced here by `jsonwebtoken`'s default `Validation`.
+fn decode_claims(
+    token: &str,
+    jwks: &jsonwebtoken::jwk::JwkSet,
+) -> Result<serde_json::Value, AuthzError> {
+    use jsonwebtoken::{decode, decode_header, Algorithm, DecodingKey, Validation};
+
+    let header = decode_header(token).map_err(|e| AuthzError::Token(e.to_string()))?;
+    let kid = header
+        .kid
+        .ok_or_else(|| AuthzError::Token("token header has no `kid`".to_string()))?;
+    let jwk = jwks
+        .find(&kid)
+        .ok_or_else(|| AuthzError::Token(format!("no JWKS key for kid {kid}")))?;
+    let key = DecodingKey::from_jwk(jwk).map_err(|e| AuthzError::Token(e.to_string()))?;
+
+    let mut validation = Validation::new(header.alg.unwrap_or(Algorithm::RS256));
+    validation.validate_aud = false;
+    let data = decode::<serde_json::Value>(token, &key, &validation)
+        .map_err(|e| AuthzError::Token(e.to_string()))?;
+    Ok(data.claims)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonwebtoken::jwk::{
+        AlgorithmParameters, CommonParameters, Jwk, JwkSet, KeyAlgorithm, OctetKeyParameters,
+        OctetKeyType,
+    };
+    use jsonwebtoken::{encode, EncodingKey, Header};
+
+    const TEST_SECRET: &[u8] = b"test-shared-secret-for-authz-unit-tests";
+    const TEST_KID: &str = "test-key-1";
+
+    /// A JWKS exposing `TEST_SECRET` as an HMAC ("oct") key under `TEST_KID`,
+    /// so tokens can be minted and verified without a network JWKS fetch.
+    fn test_jwks() -> JwkSet {
+        JwkSet {
+            keys: vec![Jwk {
+                common: CommonParameters {
+                    key_algorithm: Some(KeyAlgorithm::HS256),
+                    key_id: Some(TEST_KID.to_string()),
+                    ..Default::default()
+                },
+                algorithm: AlgorithmParameters::OctetKey(OctetKeyParameters {
+                    value: base64_url_encode(TEST_SECRET),
+                    key_type: OctetKeyType::Octet,
+                }),
+            }],
+        }
+    }
+
+    /// Minimal base64url (no padding) encoder, just enough to embed
+    /// `TEST_SECRET` in a JWK `value` field without pulling in a base64 crate
+    /// for one test helper.
+    fn base64_url_encode(bytes: &[u8]) -> String {
+        const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+        let mut out = String::with_capacity((bytes.len() + 2) / 3 * 4);
+        for chunk in bytes.chunks(3) {
+            let b0 = chunk[0] as u32;
+            let b1 = *chunk.get(1).unwrap_or(&0) as u32;
+            let b2 = *chunk.get(2).unwrap_or(&0) as u32;
+            let triple = (b0 << 16) | (b1 << 8) | b2;
+            out.push(ALPHABET[((triple >> 18) & 0x3F) as usize] as char);
+            out.push(ALPHABET[((triple >> 12) & 0x3F) as usize] as char);
+            if chunk.len() > 1 {
+                out.push(ALPHABET[((triple >> 6) & 0x3F) as usize] as char);
+            }
+            if chunk.len() > 2 {
+                out.push(ALPHABET[(triple & 0x3F) as usize] as char);
+            }
+        }
+        out
+    }
+
+    fn sign_token(kid: Option<&str>, claims: &serde_json::Value) -> String {
+        let mut header = Header::new(jsonwebtoken::Algorithm::HS256);
+        header.kid = kid.map(str::to_string);
+        encode(&header, claims, &EncodingKey::from_secret(TEST_SECRET)).unwrap()
+    }
+
+    #[test]
+    fn test_decode_claims_accepts_a_validly_signed_token() {
+        let jwks = test_jwks();
+        let token = sign_token(Some(TEST_KID), &serde_json::json!({"sub": "agent-a"}));
+        let claims = decode_claims(&token, &jwks).unwrap();
+        assert_eq!(claims["sub"], "agent-a");
+    }
+
+    #[test]
+    fn test_decode_claims_rejects_unknown_kid() {
+        let jwks = test_jwks();
+        let token = sign_token(Some("no-such-key"), &serde_json::json!({"sub": "agent-a"}));
+        assert!(matches!(decode_claims(&token, &jwks), Err(AuthzError::Token(_))));
+    }
+
+    #[test]
+    fn test_decode_claims_rejects_missing_kid() {
+        let jwks = test_jwks();
+        let token = sign_token(None, &serde_json::json!({"sub": "agent-a"}));
+        assert!(matches!(decode_claims(&token, &jwks), Err(AuthzError::Token(_))));
+    }
+
+    #[test]
+    fn test_decode_claims_rejects_expired_token() {
+        let jwks = test_jwks();
+        let expired = (chrono::Utc::now() - chrono::Duration::hours(1)).timestamp();
+        let token = sign_token(Some(TEST_KID), &serde_json::json!({"sub": "agent-a", "exp": expired}));
+        assert!(matches!(decode_claims(&token, &jwks), Err(AuthzError::Token(_))));
+    }
+
+    #[test]
+    fn test_authorize_denies_wrong_issuer_via_required_claims() {
+        let mut config = JwtAuthConfig {
+            jwks_uri: "https://example.invalid/jwks.json".to_string(),
+            userinfo_endpoint: None,
+            required_claims: HashMap::new(),
+            skill_claims: HashMap::new(),
+            policy_ref: None,
+        };
+        config
+            .required_claims
+            .insert("iss".to_string(), serde_json::json!("https://expected-issuer"));
+
+        let claims = serde_json::json!({"iss": "https://attacker-issuer", "sub": "agent-a"});
+        assert_eq!(
+            first_unmet_claim(&config.required_claims, &claims),
+            Some("iss")
+        );
+    }
+
+    #[test]
+    fn test_authorize_denies_wrong_audience_via_required_claims() {
+        let mut required = HashMap::new();
+        required.insert("aud".to_string(), serde_json::json!("expected-audience"));
+        let claims = serde_json::json!({"aud": "wrong-audience", "sub": "agent-a"});
+        assert_eq!(first_unmet_claim(&required, &claims), Some("aud"));
+    }
+
+    #[test]
+    fn test_token_checker_shared_reuses_one_instance_per_jwks_uri() {
+        let config = JwtAuthConfig {
+            jwks_uri: "https://example.invalid/shared-jwks.json".to_string(),
+            userinfo_endpoint: None,
+            required_claims: HashMap::new(),
+            skill_claims: HashMap::new(),
+            policy_ref: None,
+        };
+        let a = TokenChecker::shared(&config);
+        let b = TokenChecker::shared(&config);
+        assert!(Arc::ptr_eq(&a, &b));
+    }
+}
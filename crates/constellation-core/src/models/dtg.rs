@@ -7,7 +7,9 @@
 //! Based on research: "Data Transformation Graphs vs. Code Property Graphs"
 //! for tracking multi-agent execution provenance.
 
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 use uuid::Uuid;
 
@@ -156,6 +158,11 @@ pub struct DataTransformationGraph {
     
     /// Tags for categorization and search.
     pub tags: Vec<String>,
+
+    /// Persisted schema version of this serialized graph, folded forward to the
+    /// current shape on load by the [`migration`](super::migration) registry.
+    #[serde(default = "super::migration::dtg_schema_version")]
+    pub schema_version: u32,
 }
 
 /// Edge representing data flow between DTG nodes.
@@ -172,7 +179,13 @@ pub struct DtgEdge {
     
     /// Edge type (e.g., "data_flow", "control_flow", "dependency").
     pub edge_type: String,
-    
+
+    /// Whether this is a weak (optional) dependency: the target can still run
+    /// when a weak dependency is `Failed`/`Cancelled`, whereas a strong
+    /// dependency must reach `Completed` first.
+    #[serde(default)]
+    pub optional: bool,
+
     /// Metadata about this edge.
     pub metadata: HashMap<String, serde_json::Value>,
 }
@@ -490,6 +503,7 @@ impl DataTransformationGraph {
             completed_at: None,
             status: DtgGraphStatus::Constructing,
             tags: Vec::new(),
+            schema_version: super::migration::dtg_schema_version(),
         }
     }
     
@@ -510,10 +524,25 @@ impl DataTransformationGraph {
             target,
             data_ref,
             edge_type,
+            optional: false,
             metadata: HashMap::new(),
         };
         self.edges.push(edge);
     }
+
+    /// Add a weak (optional) edge: the target stays runnable even if `source`
+    /// ends up `Failed`/`Cancelled`, with the carried data ref surfaced as a
+    /// missing input instead of blocking the node.
+    pub fn add_weak_edge(&mut self, source: Uuid, target: Uuid, data_ref: Uuid) {
+        self.edges.push(DtgEdge {
+            source,
+            target,
+            data_ref,
+            edge_type: "weak".to_string(),
+            optional: true,
+            metadata: HashMap::new(),
+        });
+    }
     
     /// Mark the graph as ready for execution.
     pub fn mark_ready(&mut self) {
@@ -604,27 +633,345 @@ impl DtgProvenance {
         }
     }
     
-    /// Add a transformation record.
-    pub fn add_transformation(&mut self, record: TransformationRecord) {
+    /// Append a transformation record, chaining it to the current head.
+    ///
+    /// The record's `transformation_hash` is (re)computed from its canonical
+    /// contents and the previous record's hash, so `transformation_chain` forms
+    /// a hash-linked log that cannot be edited without breaking every hash
+    /// downstream of the change.
+    pub fn add_transformation(&mut self, mut record: TransformationRecord) {
+        let previous = self
+            .transformation_chain
+            .last()
+            .map(|r| r.transformation_hash.clone());
+        record.transformation_hash = record.compute_hash(previous.as_deref());
         self.transformation_chain.push(record);
     }
-    
-    /// Add a cryptographic signature.
-    pub fn add_signature(&mut self, signer: String, algorithm: String, signature: String) {
-        let crypto_signature = CryptographicSignature {
+
+    /// Sign the current head of the transformation chain with `signing_key`.
+    ///
+    /// The Ed25519 signature covers the head hash, and the verifying key is
+    /// stored in `public_key` so [`verify_signatures`](DtgProvenance::verify_signatures)
+    /// can check it without an out-of-band key exchange.
+    pub fn add_signature(&mut self, signer: String, signing_key: &SigningKey) {
+        let head = self.head_hash().unwrap_or_default();
+        let signature = signing_key.sign(head.as_bytes());
+        self.signatures.push(CryptographicSignature {
             signer,
-            algorithm,
-            signature,
-            public_key: None,
+            algorithm: "Ed25519".to_string(),
+            signature: hex::encode(signature.to_bytes()),
+            public_key: Some(hex::encode(signing_key.verifying_key().to_bytes())),
             signed_at: chrono::Utc::now(),
-        };
-        self.signatures.push(crypto_signature);
+        });
     }
-    
-    /// Verify all signatures in the provenance.
-    pub fn verify_signatures(&self) -> bool {
-        // In a real implementation, this would verify cryptographic signatures
-        // For now, return true if we have any signatures
-        !self.signatures.is_empty()
+
+    /// Hash at the head of the transformation chain, if any records exist.
+    pub fn head_hash(&self) -> Option<String> {
+        self.transformation_chain
+            .last()
+            .map(|r| r.transformation_hash.clone())
+    }
+
+    /// Recompute the hash-linked chain and confirm each stored hash matches.
+    /// Returns the recomputed head hash on success.
+    fn verify_chain(&self) -> Result<String, VerificationError> {
+        let mut previous: Option<String> = None;
+        for (index, record) in self.transformation_chain.iter().enumerate() {
+            let expected = record.compute_hash(previous.as_deref());
+            if expected != record.transformation_hash {
+                return Err(VerificationError::BrokenChainLink { index });
+            }
+            previous = Some(expected);
+        }
+        previous.ok_or(VerificationError::EmptyChain)
+    }
+
+    /// Verify the provenance is intact and authentic.
+    ///
+    /// First recomputes every [`TransformationRecord`] hash and confirms the
+    /// chain links are consistent, then verifies each [`CryptographicSignature`]
+    /// against the recomputed head using its stored verifying key. The returned
+    /// [`VerificationError`] distinguishes a broken chain link from a bad
+    /// signature so callers can tell tampering of the log apart from a forged
+    /// signature.
+    pub fn verify_signatures(&self) -> Result<(), VerificationError> {
+        let head = self.verify_chain()?;
+
+        if self.signatures.is_empty() {
+            return Err(VerificationError::NoSignatures);
+        }
+
+        for (index, sig) in self.signatures.iter().enumerate() {
+            if sig.algorithm != "Ed25519" {
+                return Err(VerificationError::UnsupportedAlgorithm {
+                    index,
+                    algorithm: sig.algorithm.clone(),
+                });
+            }
+
+            let key_hex = sig
+                .public_key
+                .as_ref()
+                .ok_or(VerificationError::MissingKey { index })?;
+            let key_bytes: [u8; 32] = hex::decode(key_hex)
+                .ok()
+                .and_then(|b| b.try_into().ok())
+                .ok_or(VerificationError::MalformedKey { index })?;
+            let verifying_key = VerifyingKey::from_bytes(&key_bytes)
+                .map_err(|_| VerificationError::MalformedKey { index })?;
+
+            let sig_bytes: [u8; 64] = hex::decode(&sig.signature)
+                .ok()
+                .and_then(|b| b.try_into().ok())
+                .ok_or(VerificationError::MalformedSignature { index })?;
+            let signature = Signature::from_bytes(&sig_bytes);
+
+            verifying_key
+                .verify(head.as_bytes(), &signature)
+                .map_err(|_| VerificationError::BadSignature { index })?;
+        }
+
+        Ok(())
+    }
+}
+
+impl TransformationRecord {
+    /// Compute this record's chained SHA-256 hash.
+    ///
+    /// The record is canonicalized into a length-framed byte string — `node_id`,
+    /// `agent_id`, `skill_id`, inputs and outputs sorted by data-ref id (each
+    /// paired with its content hash), parameters sorted by key, and the
+    /// timestamp — with the `previous` record's hash folded in so the chain is
+    /// tamper-evident. The `transformation_hash` field itself is excluded, as it
+    /// is the output of this computation.
+    pub fn compute_hash(&self, previous: Option<&str>) -> String {
+        let mut hasher = Sha256::new();
+        field(&mut hasher, self.node_id.as_bytes());
+        field(&mut hasher, self.agent_id.as_bytes());
+        field(&mut hasher, self.skill_id.as_bytes());
+
+        for refs in [&self.inputs, &self.outputs] {
+            let mut pairs: Vec<(String, String)> = refs
+                .iter()
+                .map(|r| (r.id.to_string(), r.content_hash.clone().unwrap_or_default()))
+                .collect();
+            pairs.sort();
+            for (id, hash) in &pairs {
+                field(&mut hasher, id.as_bytes());
+                field(&mut hasher, hash.as_bytes());
+            }
+        }
+
+        let mut params: Vec<(&String, String)> = self
+            .parameters
+            .iter()
+            .map(|(k, v)| (k, v.to_string()))
+            .collect();
+        params.sort_by(|a, b| a.0.cmp(b.0));
+        for (key, value) in &params {
+            field(&mut hasher, key.as_bytes());
+            field(&mut hasher, value.as_bytes());
+        }
+
+        field(&mut hasher, self.timestamp.to_rfc3339().as_bytes());
+        if let Some(previous) = previous {
+            field(&mut hasher, previous.as_bytes());
+        }
+
+        hex::encode(hasher.finalize())
+    }
+}
+
+/// Feed one length-framed field into the hasher so concatenated fields are
+/// unambiguous (no two field boundaries collapse into the same byte string).
+fn field(hasher: &mut Sha256, bytes: &[u8]) {
+    hasher.update((bytes.len() as u64).to_le_bytes());
+    hasher.update(bytes);
+}
+
+/// Error raised while verifying a [`DtgProvenance`] chain and its signatures.
+#[derive(Debug)]
+pub enum VerificationError {
+    /// The chain has no transformation records to verify.
+    EmptyChain,
+    /// A stored record hash does not match its recomputed value.
+    BrokenChainLink {
+        /// Position of the record whose hash failed.
+        index: usize,
+    },
+    /// The chain is intact but carries no signatures.
+    NoSignatures,
+    /// A signature used an algorithm other than Ed25519.
+    UnsupportedAlgorithm {
+        /// Position of the offending signature.
+        index: usize,
+        /// The declared algorithm.
+        algorithm: String,
+    },
+    /// A signature is missing its verifying key.
+    MissingKey {
+        /// Position of the offending signature.
+        index: usize,
+    },
+    /// A signature's verifying key could not be decoded.
+    MalformedKey {
+        /// Position of the offending signature.
+        index: usize,
+    },
+    /// A signature value could not be decoded.
+    MalformedSignature {
+        /// Position of the offending signature.
+        index: usize,
+    },
+    /// A signature did not verify against the chain head.
+    BadSignature {
+        /// Position of the offending signature.
+        index: usize,
+    },
+}
+
+impl std::fmt::Display for VerificationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VerificationError::EmptyChain => write!(f, "transformation chain is empty"),
+            VerificationError::BrokenChainLink { index } => {
+                write!(f, "transformation chain broken at record {index}")
+            }
+            VerificationError::NoSignatures => write!(f, "provenance carries no signatures"),
+            VerificationError::UnsupportedAlgorithm { index, algorithm } => {
+                write!(f, "signature {index} uses unsupported algorithm {algorithm}")
+            }
+            VerificationError::MissingKey { index } => {
+                write!(f, "signature {index} is missing its verifying key")
+            }
+            VerificationError::MalformedKey { index } => {
+                write!(f, "signature {index} has a malformed verifying key")
+            }
+            VerificationError::MalformedSignature { index } => {
+                write!(f, "signature {index} has a malformed signature value")
+            }
+            VerificationError::BadSignature { index } => {
+                write!(f, "signature {index} failed to verify against the chain head")
+            }
+        }
+    }
+}
+
+impl std::error::Error for VerificationError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::SigningKey;
+
+    fn record(node_id: Uuid, agent_id: &str) -> TransformationRecord {
+        TransformationRecord {
+            node_id,
+            agent_id: agent_id.to_string(),
+            skill_id: "skill".to_string(),
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+            parameters: HashMap::new(),
+            timestamp: chrono::Utc::now(),
+            transformation_hash: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_add_transformation_chains_hashes_to_the_previous_head() {
+        let mut prov = DtgProvenance::new(Uuid::new_v4());
+        prov.add_transformation(record(Uuid::new_v4(), "agent-a"));
+        let first_hash = prov.head_hash().unwrap();
+
+        prov.add_transformation(record(Uuid::new_v4(), "agent-b"));
+        let second = &prov.transformation_chain[1];
+        assert_eq!(second.compute_hash(Some(&first_hash)), second.transformation_hash);
+        assert_ne!(first_hash, second.transformation_hash);
+    }
+
+    #[test]
+    fn test_verify_signatures_succeeds_for_an_intact_signed_chain() {
+        let mut prov = DtgProvenance::new(Uuid::new_v4());
+        prov.add_transformation(record(Uuid::new_v4(), "agent-a"));
+        prov.add_transformation(record(Uuid::new_v4(), "agent-b"));
+
+        let signing_key = SigningKey::from_bytes(&[9u8; 32]);
+        prov.add_signature("agent-a".to_string(), &signing_key);
+
+        assert!(prov.verify_signatures().is_ok());
+    }
+
+    #[test]
+    fn test_verify_signatures_rejects_empty_chain() {
+        let prov = DtgProvenance::new(Uuid::new_v4());
+        assert!(matches!(
+            prov.verify_signatures(),
+            Err(VerificationError::EmptyChain)
+        ));
+    }
+
+    #[test]
+    fn test_verify_signatures_rejects_no_signatures() {
+        let mut prov = DtgProvenance::new(Uuid::new_v4());
+        prov.add_transformation(record(Uuid::new_v4(), "agent-a"));
+
+        assert!(matches!(
+            prov.verify_signatures(),
+            Err(VerificationError::NoSignatures)
+        ));
+    }
+
+    #[test]
+    fn test_verify_signatures_detects_a_broken_chain_link() {
+        let mut prov = DtgProvenance::new(Uuid::new_v4());
+        prov.add_transformation(record(Uuid::new_v4(), "agent-a"));
+        prov.add_transformation(record(Uuid::new_v4(), "agent-b"));
+
+        let signing_key = SigningKey::from_bytes(&[9u8; 32]);
+        prov.add_signature("agent-a".to_string(), &signing_key);
+
+        // Tamper with the first record after signing, breaking the hash chain
+        // without touching the signature itself.
+        prov.transformation_chain[0].agent_id = "attacker".to_string();
+
+        assert!(matches!(
+            prov.verify_signatures(),
+            Err(VerificationError::BrokenChainLink { index: 0 })
+        ));
+    }
+
+    #[test]
+    fn test_verify_signatures_rejects_a_signature_from_the_wrong_key() {
+        let mut prov = DtgProvenance::new(Uuid::new_v4());
+        prov.add_transformation(record(Uuid::new_v4(), "agent-a"));
+
+        let signing_key = SigningKey::from_bytes(&[9u8; 32]);
+        prov.add_signature("agent-a".to_string(), &signing_key);
+
+        // Swap in a different key's verifying key so the stored signature no
+        // longer matches the public key it's checked against.
+        let other_key = SigningKey::from_bytes(&[3u8; 32]);
+        prov.signatures[0].public_key =
+            Some(hex::encode(other_key.verifying_key().to_bytes()));
+
+        assert!(matches!(
+            prov.verify_signatures(),
+            Err(VerificationError::BadSignature { index: 0 })
+        ));
+    }
+
+    #[test]
+    fn test_verify_signatures_rejects_unsupported_algorithm() {
+        let mut prov = DtgProvenance::new(Uuid::new_v4());
+        prov.add_transformation(record(Uuid::new_v4(), "agent-a"));
+
+        let signing_key = SigningKey::from_bytes(&[9u8; 32]);
+        prov.add_signature("agent-a".to_string(), &signing_key);
+        prov.signatures[0].algorithm = "RSA".to_string();
+
+        assert!(matches!(
+            prov.verify_signatures(),
+            Err(VerificationError::UnsupportedAlgorithm { index: 0, .. })
+        ));
     }
 }
\ No newline at end of file
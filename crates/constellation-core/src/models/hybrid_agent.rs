@@ -38,6 +38,11 @@ pub struct HybridAgentConfig {
     
     /// Fallback strategies.
     pub fallback_strategies: Vec<FallbackStrategy>,
+
+    /// Persisted schema version of this serialized config, folded forward to the
+    /// current shape on load by the [`migration`](super::migration) registry.
+    #[serde(default = "super::migration::hybrid_agent_schema_version")]
+    pub schema_version: u32,
 }
 
 /// Strategist configuration (LLM).
@@ -259,18 +264,39 @@ pub struct ExecutorPerformance {
 pub struct ResourceRequirements {
     /// CPU cores required.
     pub cpu_cores: u32,
-    
+
     /// Memory required in MB.
     pub memory_mb: u32,
-    
+
     /// GPU memory required in MB.
     pub gpu_memory_mb: Option<u32>,
-    
+
     /// Disk space required in MB.
     pub disk_mb: u32,
-    
+
     /// Network bandwidth required in Mbps.
     pub network_mbps: u32,
+
+    /// Incremental CPU cores consumed by each additional concurrent task slot,
+    /// on top of (not instead of) the `cpu_cores` footprint already paid once
+    /// to stand the executor up. [`HybridAgentConfig::optimize_allocation`]
+    /// sizes concurrency against this instead of re-charging the whole static
+    /// footprint per task.
+    #[serde(default = "default_cpu_cores_per_task")]
+    pub cpu_cores_per_task: u32,
+
+    /// Incremental memory in MB consumed by each additional concurrent task
+    /// slot, on top of the `memory_mb` footprint already paid once.
+    #[serde(default = "default_memory_mb_per_task")]
+    pub memory_mb_per_task: u32,
+}
+
+fn default_cpu_cores_per_task() -> u32 {
+    1
+}
+
+fn default_memory_mb_per_task() -> u32 {
+    512
 }
 
 /// Coordination strategy between strategist and executors.
@@ -488,7 +514,7 @@ pub struct FallbackStrategy {
 }
 
 /// Fallback trigger condition.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "snake_case")]
 pub enum FallbackTrigger {
     HighLatency,
@@ -528,6 +554,7 @@ impl HybridAgentConfig {
             resource_allocation: ResourceAllocation::default(),
             performance_targets: PerformanceTargets::default(),
             fallback_strategies: Vec::new(),
+            schema_version: super::migration::hybrid_agent_schema_version(),
         }
     }
     
@@ -585,6 +612,170 @@ impl HybridAgentConfig {
         
         total
     }
+
+    /// Solve for per-executor concurrency and budget share that maximizes
+    /// aggregate throughput while respecting the executor budget slice, the
+    /// aggregate resource ceiling, and the latency target.
+    ///
+    /// Executors whose `avg_latency_ms` exceeds the `latency_target_ms` are
+    /// excluded from the primary pass (retained only as a feasibility fallback
+    /// when no executor meets the target). Remaining executors are ranked by
+    /// efficiency ratio (`throughput_tps / cost_per_1k_tasks`), and concurrency
+    /// is assigned greedily to the highest-ratio executor until the budget or
+    /// resource ceiling binds, or the throughput target is met.
+    pub fn optimize_allocation(&self, total_budget: f64) -> AllocationResult {
+        let targets = &self.performance_targets;
+        let executor_budget =
+            self.resource_allocation.budget_allocation.executors_percentage / 100.0 * total_budget;
+        let resource_ceiling = self.total_resource_requirements();
+
+        // Prefer executors within the latency target; fall back to all of them
+        // only when none qualify.
+        let mut latency_infeasible = false;
+        let mut candidates: Vec<&ExecutorConfig> = self
+            .executors
+            .iter()
+            .filter(|e| e.performance.avg_latency_ms <= targets.latency_target_ms)
+            .collect();
+        if candidates.is_empty() {
+            latency_infeasible = true;
+            candidates = self.executors.iter().collect();
+        }
+
+        // Efficiency ratio, descending. Executors with zero cost sort first.
+        candidates.sort_by(|a, b| {
+            let ratio = |e: &ExecutorConfig| {
+                if e.performance.cost_per_1k_tasks <= 0.0 {
+                    f64::INFINITY
+                } else {
+                    e.performance.throughput_tps / e.performance.cost_per_1k_tasks
+                }
+            };
+            ratio(b)
+                .partial_cmp(&ratio(a))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        let mut tasks: Vec<u32> = vec![0; candidates.len()];
+        let mut total_cost = 0.0;
+        let mut achieved_throughput = 0.0;
+        let mut used_cpu = 0u32;
+        let mut used_memory = 0u32;
+        let mut binding: Option<BindingConstraint> = None;
+
+        'outer: loop {
+            if achieved_throughput >= targets.throughput_target_tps {
+                break;
+            }
+            let mut progressed = false;
+            for (idx, executor) in candidates.iter().enumerate() {
+                let next_cost =
+                    total_cost + executor.performance.cost_per_1k_tasks / 1000.0;
+                // Each additional concurrent task slot costs the executor's
+                // marginal per-task footprint, not its whole static footprint
+                // again — the latter is already folded into `resource_ceiling`
+                // once via `total_resource_requirements`.
+                let next_cpu = used_cpu + executor.resource_requirements.cpu_cores_per_task;
+                let next_memory =
+                    used_memory + executor.resource_requirements.memory_mb_per_task;
+
+                if next_cost > executor_budget {
+                    binding = Some(BindingConstraint::Budget);
+                    break 'outer;
+                }
+                if next_cpu > resource_ceiling.cpu_cores
+                    || next_memory > resource_ceiling.memory_mb
+                {
+                    binding = Some(BindingConstraint::Resources);
+                    break 'outer;
+                }
+
+                tasks[idx] += 1;
+                total_cost = next_cost;
+                used_cpu = next_cpu;
+                used_memory = next_memory;
+                achieved_throughput += executor.performance.throughput_tps;
+                progressed = true;
+
+                if achieved_throughput >= targets.throughput_target_tps {
+                    break 'outer;
+                }
+            }
+            if !progressed {
+                break;
+            }
+        }
+
+        let assignments = candidates
+            .iter()
+            .zip(tasks.iter())
+            .filter(|(_, &t)| t > 0)
+            .map(|(executor, &t)| ExecutorAllocation {
+                executor_id: executor.id.clone(),
+                max_concurrent_tasks: t,
+                budget_share: if executor_budget > 0.0 {
+                    (t as f64 * executor.performance.cost_per_1k_tasks / 1000.0) / executor_budget
+                } else {
+                    0.0
+                },
+            })
+            .collect();
+
+        let target_met = achieved_throughput >= targets.throughput_target_tps;
+        let binding_constraint = if latency_infeasible && !target_met {
+            Some(BindingConstraint::Latency)
+        } else if target_met {
+            None
+        } else {
+            binding
+        };
+
+        AllocationResult {
+            assignments,
+            achieved_throughput_tps: achieved_throughput,
+            total_cost,
+            infeasible: !target_met,
+            binding_constraint,
+        }
+    }
+}
+
+/// Per-executor outcome of [`HybridAgentConfig::optimize_allocation`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ExecutorAllocation {
+    /// Executor this assignment applies to.
+    pub executor_id: String,
+    /// Concurrent task slots allocated to the executor.
+    pub max_concurrent_tasks: u32,
+    /// Share of the executor budget consumed by this assignment.
+    pub budget_share: f64,
+}
+
+/// The constraint that stopped the allocation solver short of its targets.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum BindingConstraint {
+    /// The executor budget slice was exhausted.
+    Budget,
+    /// The aggregate resource requirements ceiling was reached.
+    Resources,
+    /// Every executor breaches the latency target; only fallbacks remain.
+    Latency,
+}
+
+/// Result of solving a budget- and latency-constrained allocation.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AllocationResult {
+    /// Per-executor concurrency and budget assignment.
+    pub assignments: Vec<ExecutorAllocation>,
+    /// Aggregate throughput achieved by the assignment, in tasks per second.
+    pub achieved_throughput_tps: f64,
+    /// Total cost of the assignment.
+    pub total_cost: f64,
+    /// Whether the performance targets could not be met within the constraints.
+    pub infeasible: bool,
+    /// The binding constraint when `infeasible`, otherwise `None`.
+    pub binding_constraint: Option<BindingConstraint>,
 }
 
 impl Default for StrategistConfig {
@@ -752,6 +943,101 @@ impl Default for ResourceRequirements {
             gpu_memory_mb: Some(4096),
             disk_mb: 1024,
             network_mbps: 100,
+            cpu_cores_per_task: default_cpu_cores_per_task(),
+            memory_mb_per_task: default_memory_mb_per_task(),
         }
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An executor with all the knobs `optimize_allocation` reads, defaulting
+    /// to values that comfortably meet the latency target.
+    fn executor(
+        id: &str,
+        throughput_tps: f64,
+        cost_per_1k_tasks: f64,
+        cpu_cores_per_task: u32,
+        memory_mb_per_task: u32,
+    ) -> ExecutorConfig {
+        let mut executor = ExecutorConfig::new(id.to_string(), ExecutorDomain::CodeGeneration);
+        executor.performance.throughput_tps = throughput_tps;
+        executor.performance.cost_per_1k_tasks = cost_per_1k_tasks;
+        executor.resource_requirements.cpu_cores_per_task = cpu_cores_per_task;
+        executor.resource_requirements.memory_mb_per_task = memory_mb_per_task;
+        executor
+    }
+
+    fn config_with_executors(executors: Vec<ExecutorConfig>) -> HybridAgentConfig {
+        let mut config = HybridAgentConfig::new("agent".to_string(), "desc".to_string());
+        config.executors = executors;
+        config
+    }
+
+    #[test]
+    fn test_optimize_allocation_meets_throughput_target_in_one_task() {
+        let mut config = config_with_executors(vec![executor("exec-1", 5.0, 0.5, 1, 512)]);
+        config.performance_targets.throughput_target_tps = 2.0;
+
+        let result = config.optimize_allocation(1000.0);
+
+        assert!(!result.infeasible);
+        assert!(result.binding_constraint.is_none());
+        assert_eq!(result.assignments.len(), 1);
+        assert_eq!(result.assignments[0].max_concurrent_tasks, 1);
+        assert!(result.achieved_throughput_tps >= 2.0);
+    }
+
+    #[test]
+    fn test_optimize_allocation_binds_on_budget() {
+        // $0.5 per task against a $1.0 executor budget admits exactly 2 tasks
+        // before the third would overshoot it; resources are left generous so
+        // budget is what binds first.
+        let mut config = config_with_executors(vec![executor("exec-1", 1.0, 500.0, 1, 1)]);
+        config.resource_allocation.budget_allocation.executors_percentage = 10.0;
+        config.performance_targets.throughput_target_tps = 100.0;
+
+        let result = config.optimize_allocation(10.0);
+
+        assert!(result.infeasible);
+        assert_eq!(result.binding_constraint, Some(BindingConstraint::Budget));
+        assert_eq!(result.assignments[0].max_concurrent_tasks, 2);
+    }
+
+    #[test]
+    fn test_optimize_allocation_binds_on_resources() {
+        // The aggregate ceiling is `4 (strategist) + 2 (this executor's static
+        // cpu_cores)` = 6; at 3 cpu_cores_per_task, a third task would push
+        // usage to 9 and trips the ceiling after 2 tasks. Cost is zero so the
+        // budget never binds first.
+        let mut config = config_with_executors(vec![executor("exec-1", 1.0, 0.0, 3, 1)]);
+        config.performance_targets.throughput_target_tps = 100.0;
+
+        let result = config.optimize_allocation(1000.0);
+
+        assert!(result.infeasible);
+        assert_eq!(result.binding_constraint, Some(BindingConstraint::Resources));
+        assert_eq!(result.assignments[0].max_concurrent_tasks, 2);
+    }
+
+    #[test]
+    fn test_optimize_allocation_falls_back_to_latency_infeasible_executors() {
+        let mut config = config_with_executors(vec![executor("exec-1", 1.0, 0.5, 1, 512)]);
+        // The default executor's 2000ms avg latency breaches this, so no
+        // executor qualifies for the primary pass.
+        config.performance_targets.latency_target_ms = 10;
+        config.performance_targets.throughput_target_tps = 100.0;
+
+        let result = config.optimize_allocation(1000.0);
+
+        assert!(result.infeasible);
+        assert_eq!(result.binding_constraint, Some(BindingConstraint::Latency));
+        // The fallback pass still assigns from the (latency-breaching) candidate
+        // rather than leaving the whole allocation empty.
+        assert_eq!(result.assignments.len(), 1);
+        assert_eq!(result.assignments[0].executor_id, "exec-1");
+        assert!(result.assignments[0].max_concurrent_tasks > 0);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,372 @@
+//! Locale-aware text and lists for internationalized agent cards.
+//!
+//! [`LocalizedText`] and [`LocalizedList`] each carry a default value plus
+//! zero or more per-locale variants keyed by BCP-47 [`LanguageTag`]. They
+//! serialize back-compatibly: a plain JSON string/array round-trips as the
+//! default with no variants, while an object keyed by language tags (with a
+//! `default` key) carries localized values. This lets existing
+//! non-localized cards deserialize unchanged, because `AgentSkill.name`,
+//! `.description`, `.examples`, and `Agent.description` carry these types
+//! directly rather than through a parallel set of `localized_*` fields.
+
+use std::collections::HashMap;
+
+use serde::de::{self, MapAccess, Visitor};
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::agent::{Agent, AgentSkill};
+
+/// A BCP-47 language tag (e.g. `en`, `en-US`, `fr`).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct LanguageTag(pub String);
+
+impl From<&str> for LanguageTag {
+    fn from(s: &str) -> Self {
+        LanguageTag(s.to_string())
+    }
+}
+
+/// Text with a default value and optional per-locale variants.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct LocalizedText {
+    /// The fallback value used when a requested locale is absent.
+    pub default: String,
+    /// Per-locale variants keyed by language tag.
+    pub values: HashMap<LanguageTag, String>,
+}
+
+impl LocalizedText {
+    /// Create a localized text from just a default value.
+    pub fn new(default: impl Into<String>) -> Self {
+        Self {
+            default: default.into(),
+            values: HashMap::new(),
+        }
+    }
+
+    /// Add or replace the value for `locale`.
+    pub fn with_locale(mut self, locale: impl Into<LanguageTag>, value: impl Into<String>) -> Self {
+        self.values.insert(locale.into(), value.into());
+        self
+    }
+
+    /// Resolve the best value for `locale`, falling back to the default when
+    /// the locale (or `None`) has no variant. An exact tag match is preferred,
+    /// then a primary-subtag match (e.g. `en` for a requested `en-GB`).
+    pub fn resolve(&self, locale: Option<&LanguageTag>) -> &str {
+        let Some(locale) = locale else {
+            return &self.default;
+        };
+        if let Some(exact) = self.values.get(locale) {
+            return exact;
+        }
+        let primary = locale.0.split('-').next().unwrap_or(&locale.0);
+        self.values
+            .iter()
+            .find(|(tag, _)| tag.0.split('-').next() == Some(primary))
+            .map(|(_, v)| v.as_str())
+            .unwrap_or(&self.default)
+    }
+}
+
+impl From<&str> for LocalizedText {
+    fn from(s: &str) -> Self {
+        LocalizedText::new(s)
+    }
+}
+
+impl From<String> for LocalizedText {
+    fn from(s: String) -> Self {
+        LocalizedText::new(s)
+    }
+}
+
+/// Displays the default value, so a `LocalizedText` field can be dropped into
+/// a `{}` format string the same way the plain `String` it replaced could.
+impl std::fmt::Display for LocalizedText {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.default)
+    }
+}
+
+impl PartialEq<str> for LocalizedText {
+    fn eq(&self, other: &str) -> bool {
+        self.default == other
+    }
+}
+
+impl PartialEq<&str> for LocalizedText {
+    fn eq(&self, other: &&str) -> bool {
+        self.default == *other
+    }
+}
+
+impl Serialize for LocalizedText {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        // No localized variants → serialize as a bare string for back-compat.
+        if self.values.is_empty() {
+            return serializer.serialize_str(&self.default);
+        }
+        let mut map = serializer.serialize_map(Some(self.values.len() + 1))?;
+        map.serialize_entry("default", &self.default)?;
+        for (tag, value) in &self.values {
+            map.serialize_entry(&tag.0, value)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for LocalizedText {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct TextVisitor;
+
+        impl<'de> Visitor<'de> for TextVisitor {
+            type Value = LocalizedText;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("a string or an object of language-tag keys")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                Ok(LocalizedText::new(v))
+            }
+
+            fn visit_map<M: MapAccess<'de>>(self, mut map: M) -> Result<Self::Value, M::Error> {
+                let mut default = String::new();
+                let mut values = HashMap::new();
+                while let Some((key, value)) = map.next_entry::<String, String>()? {
+                    if key == "default" {
+                        default = value;
+                    } else {
+                        values.insert(LanguageTag(key), value);
+                    }
+                }
+                Ok(LocalizedText { default, values })
+            }
+        }
+
+        deserializer.deserialize_any(TextVisitor)
+    }
+}
+
+/// A list of strings with a default value and optional per-locale variants.
+/// The `Vec<String>` analog of [`LocalizedText`], with the same back-compat
+/// serialization contract: a bare JSON array round-trips as the default with
+/// no variants, while an object keyed by language tags (with a `default`
+/// key) carries localized lists.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct LocalizedList {
+    /// The fallback list used when a requested locale is absent.
+    pub default: Vec<String>,
+    /// Per-locale variants keyed by language tag.
+    pub values: HashMap<LanguageTag, Vec<String>>,
+}
+
+impl LocalizedList {
+    /// Create a localized list from just a default value.
+    pub fn new(default: impl Into<Vec<String>>) -> Self {
+        Self {
+            default: default.into(),
+            values: HashMap::new(),
+        }
+    }
+
+    /// Add or replace the list for `locale`.
+    pub fn with_locale(mut self, locale: impl Into<LanguageTag>, value: impl Into<Vec<String>>) -> Self {
+        self.values.insert(locale.into(), value.into());
+        self
+    }
+
+    /// Resolve the best list for `locale`, falling back to the default when
+    /// the locale (or `None`) has no variant, using the same exact-then-
+    /// primary-subtag match as [`LocalizedText::resolve`].
+    pub fn resolve(&self, locale: Option<&LanguageTag>) -> &[String] {
+        let Some(locale) = locale else {
+            return &self.default;
+        };
+        if let Some(exact) = self.values.get(locale) {
+            return exact;
+        }
+        let primary = locale.0.split('-').next().unwrap_or(&locale.0);
+        self.values
+            .iter()
+            .find(|(tag, _)| tag.0.split('-').next() == Some(primary))
+            .map(|(_, v)| v.as_slice())
+            .unwrap_or(&self.default)
+    }
+}
+
+impl From<Vec<String>> for LocalizedList {
+    fn from(v: Vec<String>) -> Self {
+        LocalizedList::new(v)
+    }
+}
+
+impl Serialize for LocalizedList {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        // No localized variants → serialize as a bare array for back-compat.
+        if self.values.is_empty() {
+            return self.default.serialize(serializer);
+        }
+        let mut map = serializer.serialize_map(Some(self.values.len() + 1))?;
+        map.serialize_entry("default", &self.default)?;
+        for (tag, value) in &self.values {
+            map.serialize_entry(&tag.0, value)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for LocalizedList {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ListVisitor;
+
+        impl<'de> Visitor<'de> for ListVisitor {
+            type Value = LocalizedList;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("an array of strings or an object of language-tag keys")
+            }
+
+            fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut default = Vec::new();
+                while let Some(item) = seq.next_element::<String>()? {
+                    default.push(item);
+                }
+                Ok(LocalizedList::new(default))
+            }
+
+            fn visit_map<M: MapAccess<'de>>(self, mut map: M) -> Result<Self::Value, M::Error> {
+                let mut default = Vec::new();
+                let mut values = HashMap::new();
+                while let Some((key, value)) = map.next_entry::<String, Vec<String>>()? {
+                    if key == "default" {
+                        default = value;
+                    } else {
+                        values.insert(LanguageTag(key), value);
+                    }
+                }
+                Ok(LocalizedList { default, values })
+            }
+        }
+
+        deserializer.deserialize_any(ListVisitor)
+    }
+}
+
+/// A skill resolved for a specific locale, with concrete strings.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedSkill {
+    /// Skill id (not localized).
+    pub id: String,
+    /// Resolved name for the requested locale.
+    pub name: String,
+    /// Resolved description for the requested locale.
+    pub description: String,
+    /// Resolved examples for the requested locale, if any.
+    pub examples: Option<Vec<String>>,
+}
+
+impl AgentSkill {
+    /// Resolve this skill's `name`/`description`/`examples` for `locale`,
+    /// falling back to their defaults when the locale has no variant.
+    pub fn resolve(&self, locale: Option<&LanguageTag>) -> ResolvedSkill {
+        ResolvedSkill {
+            id: self.id.clone(),
+            name: self.name.resolve(locale).to_string(),
+            description: self.description.resolve(locale).to_string(),
+            examples: self.examples.as_ref().map(|e| e.resolve(locale).to_vec()),
+        }
+    }
+}
+
+impl Agent {
+    /// Resolve `skill_id`'s localized fields for `locale`, or `None` if the
+    /// agent has no skill with that id.
+    pub fn localized_skill(&self, skill_id: &str, locale: Option<&LanguageTag>) -> Option<ResolvedSkill> {
+        self.get_skill(skill_id).map(|skill| skill.resolve(locale))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_string_roundtrips_as_default() {
+        let json = "\"hello\"";
+        let text: LocalizedText = serde_json::from_str(json).unwrap();
+        assert_eq!(text.default, "hello");
+        assert!(text.values.is_empty());
+        assert_eq!(serde_json::to_string(&text).unwrap(), json);
+    }
+
+    #[test]
+    fn test_localized_object_resolves_by_locale() {
+        let text = LocalizedText::new("Calculation")
+            .with_locale("fr", "Calcul")
+            .with_locale("es", "Cálculo");
+        assert_eq!(text.resolve(Some(&"fr".into())), "Calcul");
+        assert_eq!(text.resolve(Some(&"en-GB".into())), "Calculation");
+        assert_eq!(text.resolve(None), "Calculation");
+    }
+
+    #[test]
+    fn test_primary_subtag_fallback() {
+        let text = LocalizedText::new("color").with_locale("en-US", "color");
+        // A request for `en-GB` falls back to the `en-*` variant.
+        assert_eq!(text.resolve(Some(&"en-GB".into())), "color");
+    }
+
+    #[test]
+    fn test_localized_list_plain_array_roundtrips_as_default() {
+        let json = "[\"a\",\"b\"]";
+        let list: LocalizedList = serde_json::from_str(json).unwrap();
+        assert_eq!(list.default, vec!["a".to_string(), "b".to_string()]);
+        assert!(list.values.is_empty());
+        assert_eq!(serde_json::to_string(&list).unwrap(), json);
+    }
+
+    #[test]
+    fn test_localized_list_resolves_by_locale() {
+        let list = LocalizedList::new(vec!["Calculate 2 + 2".to_string()])
+            .with_locale("fr", vec!["Calculer 2 + 2".to_string()]);
+        assert_eq!(list.resolve(Some(&"fr".into())), &["Calculer 2 + 2".to_string()]);
+        assert_eq!(list.resolve(Some(&"en-GB".into())), &["Calculate 2 + 2".to_string()]);
+        assert_eq!(list.resolve(None), &["Calculate 2 + 2".to_string()]);
+    }
+
+    #[test]
+    fn test_agent_skill_resolve_falls_back_to_defaults() {
+        let skill = AgentSkill {
+            id: "calculation".to_string(),
+            name: LocalizedText::new("Calculation").with_locale("fr", "Calcul"),
+            description: "Performs calculations".to_string().into(),
+            tags: vec![],
+            examples: None,
+            input_modes: None,
+            output_modes: None,
+            security: None,
+        };
+        let resolved = skill.resolve(Some(&"fr".into()));
+        assert_eq!(resolved.name, "Calcul");
+        assert_eq!(resolved.description, "Performs calculations");
+
+        let default_resolved = skill.resolve(None);
+        assert_eq!(default_resolved.name, "Calculation");
+    }
+
+    #[test]
+    fn test_localized_skill_returns_none_for_unknown_skill() {
+        let agent = Agent::new(
+            "a".to_string(),
+            "A".to_string(),
+            "desc".to_string(),
+            "P".to_string(),
+            vec![],
+            vec![],
+        );
+        assert_eq!(agent.localized_skill("missing", None), None);
+    }
+}
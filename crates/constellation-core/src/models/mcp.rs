@@ -3,6 +3,7 @@
 //! MCP provides cryptographic provenance and security for agent communications,
 //! ensuring data integrity, authenticity, and non-repudiation.
 
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -147,7 +148,7 @@ pub struct AuditEvent {
 }
 
 /// Audit event type.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum AuditEventType {
     Authentication,
@@ -157,7 +158,7 @@ pub enum AuditEventType {
 }
 
 /// Audit severity level.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum AuditSeverity {
     Informational,
@@ -398,4 +399,265 @@ impl McpSecureEnvelope {
     pub fn set_expiration(&mut self, hours_from_now: u32) {
         self.expires_at = Some(chrono::Utc::now() + chrono::Duration::hours(hours_from_now as i64));
     }
+
+    /// Sign the envelope in place with an Ed25519 signing key.
+    ///
+    /// The signature covers a canonical, deterministic byte encoding of the
+    /// envelope's security-relevant fields (see [`McpSecureEnvelope::signing_input`])
+    /// so that a verifier reconstructing the same fields produces identical bytes.
+    /// The signer fingerprint and verifying key id are left to the caller; this
+    /// method fills in the `algorithm`, `signature`, and `signed_at` fields.
+    pub fn sign(&mut self, signing_key: &SigningKey) {
+        self.signature.algorithm = "Ed25519".to_string();
+        self.signature.signed_at = chrono::Utc::now();
+        let message = self.signing_input();
+        let signature = signing_key.sign(&message);
+        self.signature.signature = hex::encode(signature.to_bytes());
+    }
+
+    /// Verify the envelope's signature against a verifying key.
+    ///
+    /// Rejects expired envelopes, replayed nonces (when a replay cache is
+    /// provided), algorithm/key-type mismatches, and bad signatures. The replay
+    /// cache callback receives the signature nonce and must return `true` if the
+    /// nonce has been observed before; a fresh nonce is recorded as a side effect.
+    pub fn verify(
+        &self,
+        verifying_key: &VerifyingKey,
+        mut replay_cache: Option<&mut dyn FnMut(&str) -> bool>,
+    ) -> Result<(), VerifyError> {
+        if self.is_expired() {
+            return Err(VerifyError::Expired);
+        }
+
+        if self.signature.algorithm != "Ed25519" {
+            return Err(VerifyError::AlgorithmMismatch {
+                declared: self.signature.algorithm.clone(),
+            });
+        }
+
+        if let Some(cache) = replay_cache.as_mut() {
+            if cache(&self.signature.nonce) {
+                return Err(VerifyError::ReplayedNonce {
+                    nonce: self.signature.nonce.clone(),
+                });
+            }
+        }
+
+        let raw = hex::decode(&self.signature.signature)
+            .map_err(|_| VerifyError::MalformedSignature)?;
+        let bytes: [u8; 64] = raw
+            .as_slice()
+            .try_into()
+            .map_err(|_| VerifyError::MalformedSignature)?;
+        let signature = Signature::from_bytes(&bytes);
+
+        verifying_key
+            .verify(&self.signing_input(), &signature)
+            .map_err(|_| VerifyError::BadSignature)
+    }
+
+    /// Like [`verify`](Self::verify), but emits an [`AuditEntry`](crate::audit::AuditEntry)
+    /// of type `Authorization` to `sink` recording the outcome (subject to
+    /// `context`'s `events_to_log` filter), with `Warning` severity on
+    /// failure so a verification failure actually shows up in an audit trail.
+    pub fn verify_with_audit(
+        &self,
+        verifying_key: &VerifyingKey,
+        replay_cache: Option<&mut dyn FnMut(&str) -> bool>,
+        context: &McpSecurityContext,
+        sink: &dyn crate::audit::AuditSink,
+    ) -> Result<(), VerifyError> {
+        let result = self.verify(verifying_key, replay_cache);
+        let severity = if result.is_ok() {
+            AuditSeverity::Informational
+        } else {
+            AuditSeverity::Warning
+        };
+        context.emit_audit(
+            sink,
+            crate::audit::AuditEntry::new(
+                AuditEventType::Authorization,
+                severity,
+                self.sender.clone(),
+                "mcp-envelope",
+                "verify",
+                result.is_ok(),
+            )
+            .with_message_id(self.message_id),
+        );
+        result
+    }
+
+    /// Build the canonical signing input for this envelope.
+    ///
+    /// Fields are serialized in a fixed order with explicit length framing so
+    /// the encoding is deterministic and unambiguous — serde_json is *not* used
+    /// because its field ordering and whitespace are not canonical.
+    fn signing_input(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        push_field(&mut buf, self.message_id.as_bytes());
+        push_field(&mut buf, self.sender.as_bytes());
+        push_field(&mut buf, self.recipient.as_bytes());
+        push_field(&mut buf, self.message_type.as_bytes());
+        push_field(&mut buf, self.payload.ciphertext.as_bytes());
+        push_field(&mut buf, self.payload.algorithm.as_bytes());
+        push_field(&mut buf, self.payload.key_id.as_bytes());
+        push_field(&mut buf, self.sent_at.to_rfc3339().as_bytes());
+        match self.expires_at {
+            Some(expires_at) => push_field(&mut buf, expires_at.to_rfc3339().as_bytes()),
+            None => push_field(&mut buf, b""),
+        }
+        push_field(&mut buf, self.signature.nonce.as_bytes());
+        push_field(&mut buf, self.signature.signed_at.to_rfc3339().as_bytes());
+        buf
+    }
+}
+
+/// Append a length-prefixed field to a canonical byte buffer.
+fn push_field(buf: &mut Vec<u8>, field: &[u8]) {
+    buf.extend_from_slice(&(field.len() as u64).to_be_bytes());
+    buf.extend_from_slice(field);
+}
+
+/// Error returned when verifying an [`McpSecureEnvelope`] signature fails.
+#[derive(Debug, Clone, PartialEq)]
+pub enum VerifyError {
+    /// The envelope's `expires_at` is in the past.
+    Expired,
+    /// The signature nonce was seen before (replay attack).
+    ReplayedNonce { nonce: String },
+    /// The declared algorithm does not match the verifying key type.
+    AlgorithmMismatch { declared: String },
+    /// The signature string was not valid hex / wrong length.
+    MalformedSignature,
+    /// The signature did not verify against the key.
+    BadSignature,
+}
+
+impl std::fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VerifyError::Expired => write!(f, "envelope has expired"),
+            VerifyError::ReplayedNonce { nonce } => write!(f, "nonce already seen: {nonce}"),
+            VerifyError::AlgorithmMismatch { declared } => {
+                write!(f, "signature algorithm {declared} does not match key type")
+            }
+            VerifyError::MalformedSignature => write!(f, "malformed signature encoding"),
+            VerifyError::BadSignature => write!(f, "signature verification failed"),
+        }
+    }
+}
+
+impl std::error::Error for VerifyError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::SigningKey;
+
+    fn sample_envelope() -> McpSecureEnvelope {
+        let payload = McpEncryptedMessage {
+            ciphertext: "deadbeef".to_string(),
+            algorithm: "AES-256-GCM".to_string(),
+            iv: Some("000102030405060708090a0b".to_string()),
+            key_id: "key-1".to_string(),
+        };
+        let signature = McpSignature {
+            signer: "agent-a".to_string(),
+            algorithm: "Ed25519".to_string(),
+            signature: String::new(),
+            signed_at: chrono::Utc::now(),
+            nonce: "nonce-1".to_string(),
+            key_id: "key-1".to_string(),
+        };
+        McpSecureEnvelope::new(
+            "agent-a".to_string(),
+            "agent-b".to_string(),
+            "task".to_string(),
+            payload,
+            signature,
+        )
+    }
+
+    #[test]
+    fn test_sign_and_verify_roundtrip() {
+        let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+        let mut envelope = sample_envelope();
+        envelope.sign(&signing_key);
+
+        assert!(envelope.verify(&signing_key.verifying_key(), None).is_ok());
+    }
+
+    #[test]
+    fn test_verify_rejects_tampered_payload() {
+        let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+        let mut envelope = sample_envelope();
+        envelope.sign(&signing_key);
+        envelope.payload.ciphertext = "c0ffee".to_string();
+
+        assert_eq!(
+            envelope.verify(&signing_key.verifying_key(), None),
+            Err(VerifyError::BadSignature)
+        );
+    }
+
+    #[test]
+    fn test_verify_rejects_replayed_nonce() {
+        let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+        let mut envelope = sample_envelope();
+        envelope.sign(&signing_key);
+
+        let mut seen: std::collections::HashSet<String> = std::collections::HashSet::new();
+        let mut cache = |nonce: &str| !seen.insert(nonce.to_string());
+
+        assert!(envelope
+            .verify(&signing_key.verifying_key(), Some(&mut cache))
+            .is_ok());
+        assert_eq!(
+            envelope.verify(&signing_key.verifying_key(), Some(&mut cache)),
+            Err(VerifyError::ReplayedNonce {
+                nonce: "nonce-1".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_verify_with_audit_records_failure() {
+        use crate::audit::RingBufferSink;
+
+        let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+        let mut envelope = sample_envelope();
+        envelope.sign(&signing_key);
+        envelope.payload.ciphertext = "c0ffee".to_string();
+
+        let context = McpSecurityContext::new(SecurityLevel::High);
+        let sink = RingBufferSink::new(8);
+        let result = envelope.verify_with_audit(&signing_key.verifying_key(), None, &context, &sink);
+
+        assert_eq!(result, Err(VerifyError::BadSignature));
+        let entries = sink.entries();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].event_type, AuditEventType::Authorization);
+        assert!(!entries[0].success);
+        assert_eq!(entries[0].message_id, Some(envelope.message_id));
+    }
+
+    #[test]
+    fn test_verify_with_audit_does_not_record_default_filtered_success() {
+        use crate::audit::RingBufferSink;
+
+        let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+        let mut envelope = sample_envelope();
+        envelope.sign(&signing_key);
+
+        let context = McpSecurityContext::new(SecurityLevel::High);
+        let sink = RingBufferSink::new(8);
+        let result = envelope.verify_with_audit(&signing_key.verifying_key(), None, &context, &sink);
+
+        assert!(result.is_ok());
+        // The default AuditLogging config has log_success = false for
+        // Authorization events, so a clean verify leaves no audit entry.
+        assert!(sink.entries().is_empty());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,254 @@
+//! Versioned schema migration for persisted config structs.
+//!
+//! [`Agent`](super::agent::Agent), [`HybridAgentConfig`](super::hybrid_agent::HybridAgentConfig)
+//! and [`DataTransformationGraph`](super::dtg::DataTransformationGraph) are all
+//! serialized to JSON and persisted. When the structs evolve, older documents
+//! still have to load. Each top-level config carries a `schema_version`, and a
+//! [`MigrationRegistry`] holds ordered `vN → vN+1` conversion functions. On load
+//! the stored version is detected and folded forward through the chain to the
+//! current shape, so persisted definitions stay loadable across releases.
+//!
+//! Migrations operate on [`serde_json::Value`] — the same multi-version actor
+//! interface pattern of keeping old shapes behind the public type, except the
+//! old shapes live as transformations (rename a field, default a new
+//! [`FallbackStrategy`](super::hybrid_agent::FallbackStrategy), split an enum
+//! variant) rather than a parallel `vN` struct per release. A version gap with
+//! no registered step fails with a clear [`MigrationError::MissingMigration`].
+
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+/// A type that is persisted with a schema version and can be folded forward.
+pub trait Versioned: DeserializeOwned {
+    /// Stable name of the schema, used in error messages and the registry.
+    const SCHEMA_NAME: &'static str;
+
+    /// The schema version understood by the current struct definition.
+    const CURRENT_VERSION: u32;
+}
+
+/// Current schema version of [`Agent`](super::agent::Agent).
+pub fn agent_schema_version() -> u32 {
+    super::agent::Agent::CURRENT_VERSION
+}
+
+/// Current schema version of [`HybridAgentConfig`](super::hybrid_agent::HybridAgentConfig).
+pub fn hybrid_agent_schema_version() -> u32 {
+    super::hybrid_agent::HybridAgentConfig::CURRENT_VERSION
+}
+
+/// Current schema version of [`DataTransformationGraph`](super::dtg::DataTransformationGraph).
+pub fn dtg_schema_version() -> u32 {
+    super::dtg::DataTransformationGraph::CURRENT_VERSION
+}
+
+impl Versioned for super::agent::Agent {
+    const SCHEMA_NAME: &'static str = "Agent";
+    const CURRENT_VERSION: u32 = 1;
+}
+
+impl Versioned for super::hybrid_agent::HybridAgentConfig {
+    const SCHEMA_NAME: &'static str = "HybridAgentConfig";
+    const CURRENT_VERSION: u32 = 2;
+}
+
+impl Versioned for super::dtg::DataTransformationGraph {
+    const SCHEMA_NAME: &'static str = "DataTransformationGraph";
+    const CURRENT_VERSION: u32 = 1;
+}
+
+/// Error raised while migrating a persisted config forward.
+#[derive(Debug)]
+pub enum MigrationError {
+    /// No migration is registered to bridge a stored version to the next one.
+    MissingMigration {
+        /// Schema name of the config being loaded.
+        schema: &'static str,
+        /// Stored version that could not be advanced.
+        from: u32,
+        /// Version the chain needs to reach.
+        to: u32,
+    },
+    /// A stored version is newer than the current struct understands.
+    FutureVersion {
+        /// Schema name of the config being loaded.
+        schema: &'static str,
+        /// Stored version.
+        stored: u32,
+        /// Version understood by this build.
+        current: u32,
+    },
+    /// A migration step produced a shape that no longer deserializes.
+    Apply(String),
+}
+
+impl std::fmt::Display for MigrationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MigrationError::MissingMigration { schema, from, to } => write!(
+                f,
+                "no migration registered for {schema} v{from} -> v{to}"
+            ),
+            MigrationError::FutureVersion {
+                schema,
+                stored,
+                current,
+            } => write!(
+                f,
+                "{schema} stored at v{stored} is newer than supported v{current}"
+            ),
+            MigrationError::Apply(msg) => write!(f, "migration failed: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for MigrationError {}
+
+/// A single `from → from + 1` transformation over the serialized form.
+type Step = Box<dyn Fn(Value) -> Result<Value, MigrationError> + Send + Sync>;
+
+/// Ordered registry of `vN → vN+1` migrations for one [`Versioned`] type.
+pub struct MigrationRegistry<T: Versioned> {
+    /// `steps[i]` folds version `i` forward to version `i + 1`.
+    steps: Vec<Option<Step>>,
+    _marker: std::marker::PhantomData<fn() -> T>,
+}
+
+impl<T: Versioned> Default for MigrationRegistry<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Versioned> MigrationRegistry<T> {
+    /// Create an empty registry. The `schema_version` field defaults to the
+    /// current version, so documents already at the head need no steps.
+    pub fn new() -> Self {
+        Self {
+            steps: Vec::new(),
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    /// Register a step folding `from` to `from + 1`. Later registration for the
+    /// same `from` replaces the earlier one.
+    pub fn register<F>(mut self, from: u32, step: F) -> Self
+    where
+        F: Fn(Value) -> Result<Value, MigrationError> + Send + Sync + 'static,
+    {
+        let idx = from as usize;
+        if self.steps.len() <= idx {
+            self.steps.resize_with(idx + 1, || None);
+        }
+        self.steps[idx] = Some(Box::new(step));
+        self
+    }
+
+    /// Detect the stored version of `value`, fold it forward to the current
+    /// version, then deserialize into `T`.
+    pub fn migrate(&self, mut value: Value) -> Result<T, MigrationError> {
+        let stored = value
+            .get("schema_version")
+            .and_then(Value::as_u64)
+            .map(|v| v as u32)
+            .unwrap_or(1);
+
+        if stored > T::CURRENT_VERSION {
+            return Err(MigrationError::FutureVersion {
+                schema: T::SCHEMA_NAME,
+                stored,
+                current: T::CURRENT_VERSION,
+            });
+        }
+
+        for from in stored..T::CURRENT_VERSION {
+            let step = self
+                .steps
+                .get(from as usize)
+                .and_then(Option::as_ref)
+                .ok_or(MigrationError::MissingMigration {
+                    schema: T::SCHEMA_NAME,
+                    from,
+                    to: from + 1,
+                })?;
+            value = step(value)?;
+            if let Some(obj) = value.as_object_mut() {
+                obj.insert("schema_version".into(), Value::from(from + 1));
+            }
+        }
+
+        serde_json::from_value(value).map_err(|e| MigrationError::Apply(e.to_string()))
+    }
+}
+
+/// Migrations for [`HybridAgentConfig`](super::hybrid_agent::HybridAgentConfig).
+///
+/// v1 → v2: `fallback_strategies` was added to the struct after its initial
+/// release without a `#[serde(default)]`, so v1 documents that predate it are
+/// missing the field entirely rather than carrying an empty list. The step
+/// inserts one so those documents still deserialize.
+pub fn hybrid_agent_migrations() -> MigrationRegistry<super::hybrid_agent::HybridAgentConfig> {
+    MigrationRegistry::new().register(1, |mut value: Value| {
+        if let Some(obj) = value.as_object_mut() {
+            obj.entry("fallback_strategies")
+                .or_insert_with(|| Value::Array(Vec::new()));
+        }
+        Ok(value)
+    })
+}
+
+/// Deserialize `json` into a [`Versioned`] config, folding older documents
+/// forward through `registry` first. Documents already at the current version
+/// (the default when the field is absent) pass straight through.
+pub fn load_versioned<T: Versioned>(
+    registry: &MigrationRegistry<T>,
+    json: &str,
+) -> Result<T, MigrationError> {
+    let value: Value = serde_json::from_str(json).map_err(|e| MigrationError::Apply(e.to_string()))?;
+    registry.migrate(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::hybrid_agent::HybridAgentConfig;
+
+    #[test]
+    fn test_load_versioned_folds_v1_hybrid_agent_forward() {
+        // Simulate a v1 document predating `fallback_strategies`: take a
+        // current config, then strip `schema_version` (defaults to 1) and
+        // `fallback_strategies` entirely, since v1 documents never had them.
+        let current = HybridAgentConfig::new("legacy-agent".to_string(), "predates fallback strategies".to_string());
+        let mut old_value = serde_json::to_value(&current).unwrap();
+        let obj = old_value.as_object_mut().unwrap();
+        obj.remove("schema_version");
+        obj.remove("fallback_strategies");
+        let old_json = old_value.to_string();
+
+        let registry = hybrid_agent_migrations();
+        let migrated: HybridAgentConfig = load_versioned(&registry, &old_json).unwrap();
+
+        assert_eq!(migrated.name, "legacy-agent");
+        assert!(migrated.fallback_strategies.is_empty());
+        assert_eq!(migrated.schema_version, HybridAgentConfig::CURRENT_VERSION);
+    }
+
+    #[test]
+    fn test_migrate_rejects_a_version_newer_than_current() {
+        let registry = hybrid_agent_migrations();
+        let future = serde_json::json!({ "schema_version": HybridAgentConfig::CURRENT_VERSION + 1 });
+        let err = registry.migrate(future).unwrap_err();
+        assert!(matches!(err, MigrationError::FutureVersion { .. }));
+    }
+
+    #[test]
+    fn test_migrate_fails_clearly_when_no_step_bridges_a_gap() {
+        let empty_registry: MigrationRegistry<HybridAgentConfig> = MigrationRegistry::new();
+        let old = serde_json::json!({ "schema_version": 1 });
+        let err = empty_registry.migrate(old).unwrap_err();
+        assert!(matches!(
+            err,
+            MigrationError::MissingMigration { from: 1, to: 2, .. }
+        ));
+    }
+}
@@ -1,12 +1,28 @@
 //! Data models for the Constellation platform.
 
 pub mod agent;
+pub mod analysis;
+pub mod arrow_export;
+pub mod authz;
 pub mod dtg;
 pub mod mcp;
 pub mod hybrid_agent;
+pub mod localization;
+pub mod migration;
+pub mod prov;
+pub mod remote_attestation;
+pub mod security;
 
 // Re-export the agent types.
-pub use agent::{Agent, AgentCapabilities, AgentContact, AgentInterface, AgentProvider, AgentSkill, ProtocolBinding, SecuritySchemeType};
-pub use dtg::{DataTransformationGraph, DtgNode, DtgDataRef, DtgNodeStatus, DtgMetrics, DtgEdge, DtgGraphStatus, DtgProvenance};
-pub use mcp::{McpSecurityContext, SecurityLevel, McpAlgorithms, KeyManagement, AccessControl, AuditLogging, McpSecureEnvelope, McpEncryptedMessage, McpSignature};
-pub use hybrid_agent::{HybridAgentConfig, StrategistConfig, ExecutorConfig, CoordinationStrategy, ResourceAllocation, PerformanceTargets};
+pub use agent::{Agent, AgentCapabilities, AgentContact, AgentInterface, AgentProvider, AgentSkill, ProtocolBinding, SecuritySchemeType, CapabilityNegotiation};
+pub use dtg::{DataTransformationGraph, DtgNode, DtgDataRef, DtgNodeStatus, DtgMetrics, DtgEdge, DtgGraphStatus, DtgProvenance, VerificationError};
+pub use mcp::{McpSecurityContext, SecurityLevel, McpAlgorithms, KeyManagement, AccessControl, AuditLogging, McpSecureEnvelope, McpEncryptedMessage, McpSignature, VerifyError};
+pub use hybrid_agent::{HybridAgentConfig, StrategistConfig, ExecutorConfig, CoordinationStrategy, ResourceAllocation, PerformanceTargets, AllocationResult, ExecutorAllocation, BindingConstraint};
+pub use security::{SecurityScheme, ApiKeyLocation, OAuth2Flows, OpenIdConfiguration};
+pub use localization::{LanguageTag, LocalizedText, ResolvedSkill};
+pub use remote_attestation::{AttestationError, AttestationEvidence, AttestationVerdict};
+pub use analysis::{Bottleneck, CriticalPath};
+pub use arrow_export::{graphs_to_record_batches, DtgArrowWriter, DtgRecordBatches};
+pub use migration::{load_versioned, MigrationError, MigrationRegistry, Versioned};
+pub use prov::ProvDocument;
+pub use authz::{AuthzDecision, AuthzError, JwtAuthConfig, TokenChecker};
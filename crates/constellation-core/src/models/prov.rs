@@ -0,0 +1,487 @@
+//! W3C PROV export for [`DataTransformationGraph`].
+//!
+//! A DTG already captures what a provenance graph records: each [`DtgNode`] is a
+//! skill execution performed by an agent, and each [`DtgDataRef`] is a concrete
+//! piece of data. [`DataTransformationGraph::to_prov`] maps this onto the W3C
+//! PROV data model — nodes become `Activity`s, `agent_id`s become `Agent`s, and
+//! data refs become `Entity`s keyed by content hash — and emits both PROV-JSON
+//! and PROV-O/RDF-Turtle so DTGs can be ingested by standard provenance tooling.
+
+use std::collections::BTreeMap;
+
+use serde_json::{json, Value};
+
+use super::dtg::{DataTransformationGraph, DtgDataRef, DtgProvenance};
+
+/// The default namespace prefix used for Constellation PROV identifiers.
+const NS: &str = "cns";
+
+/// A PROV document built from a [`DataTransformationGraph`], ready to serialize
+/// as PROV-JSON or PROV-O Turtle.
+#[derive(Debug, Clone)]
+pub struct ProvDocument {
+    activities: BTreeMap<String, ProvActivity>,
+    agents: BTreeMap<String, ()>,
+    entities: BTreeMap<String, ProvEntity>,
+    used: Vec<(String, String)>,
+    was_generated_by: Vec<(String, String)>,
+    was_associated_with: Vec<(String, String)>,
+    was_derived_from: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone)]
+struct ProvActivity {
+    skill_id: String,
+    started_at: String,
+    ended_at: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+struct ProvEntity {
+    data_type: String,
+    content_hash: Option<String>,
+    storage_ref: Option<String>,
+}
+
+/// The PROV identifier for a data ref, keyed by content hash when available so
+/// identical data produced by different nodes collapses to one entity.
+fn entity_id(data_ref: &DtgDataRef) -> String {
+    match &data_ref.content_hash {
+        Some(hash) => format!("{NS}:entity/{hash}"),
+        None => format!("{NS}:entity/{}", data_ref.id),
+    }
+}
+
+impl DataTransformationGraph {
+    /// Build a W3C PROV document from this graph's nodes, agents, and data refs.
+    pub fn to_prov(&self) -> ProvDocument {
+        let mut doc = ProvDocument {
+            activities: BTreeMap::new(),
+            agents: BTreeMap::new(),
+            entities: BTreeMap::new(),
+            used: Vec::new(),
+            was_generated_by: Vec::new(),
+            was_associated_with: Vec::new(),
+            was_derived_from: Vec::new(),
+        };
+
+        for node in self.nodes.values() {
+            let activity = format!("{NS}:node/{}", node.id);
+            doc.activities.insert(
+                activity.clone(),
+                ProvActivity {
+                    skill_id: node.skill_id.clone(),
+                    started_at: node.started_at.to_rfc3339(),
+                    ended_at: node.completed_at.map(|t| t.to_rfc3339()),
+                },
+            );
+
+            let agent = format!("{NS}:agent/{}", node.agent_id);
+            doc.agents.entry(agent.clone()).or_insert(());
+            doc.was_associated_with.push((activity.clone(), agent));
+
+            for input in &node.inputs {
+                let entity = entity_id(input);
+                doc.entities.entry(entity.clone()).or_insert_with(|| entity_of(input));
+                doc.used.push((activity.clone(), entity));
+            }
+
+            for output in &node.outputs {
+                let entity = entity_id(output);
+                doc.entities.entry(entity.clone()).or_insert_with(|| entity_of(output));
+                doc.was_generated_by.push((entity.clone(), activity.clone()));
+                // Each produced entity is derived from the node's inputs.
+                for input in &node.inputs {
+                    doc.was_derived_from.push((entity.clone(), entity_id(input)));
+                }
+            }
+        }
+
+        doc
+    }
+}
+
+fn entity_of(data_ref: &DtgDataRef) -> ProvEntity {
+    ProvEntity {
+        data_type: data_ref.data_type.clone(),
+        content_hash: data_ref.content_hash.clone(),
+        storage_ref: data_ref.storage_ref.clone(),
+    }
+}
+
+impl ProvDocument {
+    /// Serialize to the [PROV-JSON](https://www.w3.org/submissions/prov-json/)
+    /// representation.
+    pub fn to_prov_json(&self) -> Value {
+        let mut activity = serde_json::Map::new();
+        for (id, a) in &self.activities {
+            let mut obj = serde_json::Map::new();
+            obj.insert("prov:startTime".into(), json!(a.started_at));
+            if let Some(ended) = &a.ended_at {
+                obj.insert("prov:endTime".into(), json!(ended));
+            }
+            obj.insert(format!("{NS}:skillId"), json!(a.skill_id));
+            activity.insert(id.clone(), Value::Object(obj));
+        }
+
+        let agent: serde_json::Map<String, Value> = self
+            .agents
+            .keys()
+            .map(|id| (id.clone(), json!({})))
+            .collect();
+
+        let mut entity = serde_json::Map::new();
+        for (id, e) in &self.entities {
+            let mut obj = serde_json::Map::new();
+            obj.insert(format!("{NS}:dataType"), json!(e.data_type));
+            if let Some(hash) = &e.content_hash {
+                obj.insert(format!("{NS}:contentHash"), json!(hash));
+            }
+            if let Some(storage) = &e.storage_ref {
+                obj.insert(format!("{NS}:storageRef"), json!(storage));
+            }
+            entity.insert(id.clone(), Value::Object(obj));
+        }
+
+        let used = relation_map(&self.used, "prov:activity", "prov:entity", "u");
+        let generated = relation_map(&self.was_generated_by, "prov:entity", "prov:activity", "wGB");
+        let associated =
+            relation_map(&self.was_associated_with, "prov:activity", "prov:agent", "wAW");
+        let derived =
+            relation_map(&self.was_derived_from, "prov:generatedEntity", "prov:usedEntity", "wDF");
+
+        json!({
+            "prefix": { NS: format!("https://constellation.example/prov/{NS}#") },
+            "activity": activity,
+            "agent": agent,
+            "entity": entity,
+            "used": used,
+            "wasGeneratedBy": generated,
+            "wasAssociatedWith": associated,
+            "wasDerivedFrom": derived,
+        })
+    }
+
+    /// Serialize to [PROV-O](https://www.w3.org/TR/prov-o/) as RDF Turtle.
+    pub fn to_turtle(&self) -> String {
+        let mut out = String::new();
+        out.push_str("@prefix prov: <http://www.w3.org/ns/prov#> .\n");
+        out.push_str(&format!(
+            "@prefix {NS}: <https://constellation.example/prov/{NS}#> .\n"
+        ));
+        out.push_str("@prefix xsd: <http://www.w3.org/2001/XMLSchema#> .\n\n");
+
+        for (id, a) in &self.activities {
+            out.push_str(&format!("{id} a prov:Activity ;\n"));
+            out.push_str(&format!(
+                "    prov:startedAtTime \"{}\"^^xsd:dateTime ;\n",
+                a.started_at
+            ));
+            if let Some(ended) = &a.ended_at {
+                out.push_str(&format!(
+                    "    prov:endedAtTime \"{ended}\"^^xsd:dateTime ;\n"
+                ));
+            }
+            out.push_str(&format!("    {NS}:skillId \"{}\" .\n", a.skill_id));
+        }
+        for id in self.agents.keys() {
+            out.push_str(&format!("{id} a prov:Agent .\n"));
+        }
+        for (id, e) in &self.entities {
+            out.push_str(&format!("{id} a prov:Entity ;\n"));
+            out.push_str(&format!("    {NS}:dataType \"{}\" .\n", e.data_type));
+        }
+        for (activity, entity) in &self.used {
+            out.push_str(&format!("{activity} prov:used {entity} .\n"));
+        }
+        for (entity, activity) in &self.was_generated_by {
+            out.push_str(&format!("{entity} prov:wasGeneratedBy {activity} .\n"));
+        }
+        for (activity, agent) in &self.was_associated_with {
+            out.push_str(&format!("{activity} prov:wasAssociatedWith {agent} .\n"));
+        }
+        for (generated, used) in &self.was_derived_from {
+            out.push_str(&format!("{generated} prov:wasDerivedFrom {used} .\n"));
+        }
+        out
+    }
+}
+
+/// Build a PROV-JSON relation object keyed by a generated `prefixN` id.
+fn relation_map(
+    pairs: &[(String, String)],
+    from_key: &str,
+    to_key: &str,
+    prefix: &str,
+) -> Value {
+    let mut map = serde_json::Map::new();
+    for (i, (from, to)) in pairs.iter().enumerate() {
+        map.insert(
+            format!("_:{prefix}{i}"),
+            json!({ from_key: from, to_key: to }),
+        );
+    }
+    Value::Object(map)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::dtg::{DtgDataRef, DtgNode, TransformationRecord};
+
+    fn data_ref(content_hash: Option<&str>) -> DtgDataRef {
+        DtgDataRef {
+            id: uuid::Uuid::new_v4(),
+            data_type: "json".to_string(),
+            schema: None,
+            size_bytes: Some(42),
+            content_hash: content_hash.map(|h| h.to_string()),
+            storage_ref: None,
+        }
+    }
+
+    fn graph_with_one_transformation() -> (DataTransformationGraph, uuid::Uuid) {
+        let mut graph = DataTransformationGraph::new("test".to_string());
+        let mut node = DtgNode::new("summarize".to_string(), "agent-a".to_string());
+        node.add_input(data_ref(Some("input-hash")));
+        node.add_output(data_ref(Some("output-hash")));
+        let node_id = graph.add_node(node);
+        (graph, node_id)
+    }
+
+    #[test]
+    fn test_to_prov_json_includes_activity_agent_and_entities() {
+        let (graph, node_id) = graph_with_one_transformation();
+        let doc = graph.to_prov();
+        let json = doc.to_prov_json();
+
+        let activity_key = format!("cns:node/{node_id}");
+        assert!(json["activity"].get(&activity_key).is_some());
+        assert_eq!(
+            json["activity"][activity_key.as_str()]["cns:skillId"],
+            "summarize"
+        );
+        assert!(json["agent"].get("cns:agent/agent-a").is_some());
+        assert!(json["entity"].get("cns:entity/input-hash").is_some());
+        assert!(json["entity"].get("cns:entity/output-hash").is_some());
+        assert_eq!(json["used"].as_object().unwrap().len(), 1);
+        assert_eq!(json["wasGeneratedBy"].as_object().unwrap().len(), 1);
+        assert_eq!(json["wasAssociatedWith"].as_object().unwrap().len(), 1);
+        assert_eq!(json["wasDerivedFrom"].as_object().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_entity_id_collapses_on_content_hash() {
+        // Two distinct data refs sharing a content hash should map to the
+        // same PROV entity, rather than one per `DtgDataRef::id`.
+        let mut graph = DataTransformationGraph::new("dedup".to_string());
+        let mut producer = DtgNode::new("produce".to_string(), "agent-a".to_string());
+        producer.add_output(data_ref(Some("shared-hash")));
+        graph.add_node(producer);
+
+        let mut consumer = DtgNode::new("consume".to_string(), "agent-b".to_string());
+        consumer.add_input(data_ref(Some("shared-hash")));
+        graph.add_node(consumer);
+
+        let doc = graph.to_prov();
+        let json = doc.to_prov_json();
+        assert_eq!(json["entity"].as_object().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_to_turtle_emits_expected_triples() {
+        let (graph, node_id) = graph_with_one_transformation();
+        let turtle = graph.to_prov().to_turtle();
+
+        assert!(turtle.contains(&format!("cns:node/{node_id} a prov:Activity")));
+        assert!(turtle.contains("cns:agent/agent-a a prov:Agent ."));
+        assert!(turtle.contains("cns:entity/input-hash a prov:Entity"));
+        assert!(turtle.contains(&format!(
+            "cns:node/{node_id} prov:wasAssociatedWith cns:agent/agent-a ."
+        )));
+    }
+
+    #[test]
+    fn test_to_prov_jsonld_includes_transformation_hash_when_recorded() {
+        let (graph, node_id) = graph_with_one_transformation();
+        let mut prov = DtgProvenance::new(graph.id);
+        prov.add_transformation(TransformationRecord {
+            node_id,
+            agent_id: "agent-a".to_string(),
+            skill_id: "summarize".to_string(),
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+            parameters: std::collections::HashMap::new(),
+            timestamp: chrono::Utc::now(),
+            transformation_hash: String::new(),
+        });
+
+        let doc = prov.to_prov_jsonld(&graph);
+        let activity = doc["@graph"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|n| n["@id"] == format!("cns:node/{node_id}"))
+            .unwrap();
+        assert!(activity.get("cns:transformationHash").is_some());
+    }
+
+    #[test]
+    fn test_to_prov_jsonld_omits_transformation_hash_when_not_recorded() {
+        let (graph, node_id) = graph_with_one_transformation();
+        let prov = DtgProvenance::new(graph.id);
+
+        let doc = prov.to_prov_jsonld(&graph);
+        let activity = doc["@graph"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|n| n["@id"] == format!("cns:node/{node_id}"))
+            .unwrap();
+        assert!(activity.get("cns:transformationHash").is_none());
+    }
+}
+
+impl DtgProvenance {
+    /// Export this provenance record as [PROV-O](https://www.w3.org/TR/prov-o/)
+    /// in JSON-LD, using `graph` for the node/edge/data-ref topology.
+    ///
+    /// Each [`DtgNode`](super::dtg::DtgNode) maps to a `prov:Activity` carrying
+    /// `prov:startedAtTime`/`prov:endedAtTime`; each
+    /// [`DtgDataRef`](DtgDataRef) maps to a `prov:Entity` keyed by its UUID; each
+    /// `data_flow` edge yields a `prov:used`/`prov:wasGeneratedBy` pair; and each
+    /// distinct `agent_id` maps to a `prov:Agent` linked via
+    /// `prov:wasAssociatedWith`. The emitted `@context` makes the document a
+    /// valid JSON-LD serialization of the PROV-O vocabulary.
+    pub fn to_prov_jsonld(&self, graph: &DataTransformationGraph) -> Value {
+        // transformation_hash per node, when the provenance chain recorded one.
+        let hashes: BTreeMap<String, String> = self
+            .transformation_chain
+            .iter()
+            .map(|r| (r.node_id.to_string(), r.transformation_hash.clone()))
+            .collect();
+
+        let mut agents: BTreeMap<String, ()> = BTreeMap::new();
+        let mut entities: BTreeMap<String, Value> = BTreeMap::new();
+        // activity id -> list of used entity ids.
+        let mut used: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        // entity id -> generating activity id.
+        let mut generated: BTreeMap<String, String> = BTreeMap::new();
+
+        let mut register_entity = |data_ref: &DtgDataRef| {
+            let id = format!("{NS}:entity/{}", data_ref.id);
+            entities.entry(id.clone()).or_insert_with(|| {
+                let mut obj = serde_json::Map::new();
+                obj.insert("@id".into(), json!(id));
+                obj.insert("@type".into(), json!("prov:Entity"));
+                obj.insert(format!("{NS}:dataType"), json!(data_ref.data_type));
+                if let Some(hash) = &data_ref.content_hash {
+                    obj.insert(format!("{NS}:contentHash"), json!(hash));
+                }
+                if let Some(size) = data_ref.size_bytes {
+                    obj.insert(format!("{NS}:sizeBytes"), json!(size));
+                }
+                Value::Object(obj)
+            });
+            id
+        };
+
+        for r in &graph.graph_inputs {
+            register_entity(r);
+        }
+        for r in &graph.graph_outputs {
+            register_entity(r);
+        }
+
+        let mut activities: Vec<Value> = Vec::new();
+        for node in graph.nodes.values() {
+            let activity = format!("{NS}:node/{}", node.id);
+            let agent = format!("{NS}:agent/{}", node.agent_id);
+            agents.entry(agent.clone()).or_insert(());
+
+            for input in &node.inputs {
+                register_entity(input);
+            }
+            for output in &node.outputs {
+                register_entity(output);
+            }
+
+            let mut obj = serde_json::Map::new();
+            obj.insert("@id".into(), json!(activity));
+            obj.insert("@type".into(), json!("prov:Activity"));
+            obj.insert(
+                "prov:startedAtTime".into(),
+                json!(node.started_at.to_rfc3339()),
+            );
+            if let Some(ended) = node.completed_at {
+                obj.insert("prov:endedAtTime".into(), json!(ended.to_rfc3339()));
+            }
+            obj.insert(format!("{NS}:skillId"), json!(node.skill_id));
+            obj.insert(
+                "prov:wasAssociatedWith".into(),
+                json!({ "@id": agent }),
+            );
+            if let Some(hash) = hashes.get(&node.id.to_string()) {
+                obj.insert(format!("{NS}:transformationHash"), json!(hash));
+            }
+            activities.push(Value::Object(obj));
+        }
+
+        // Data-flow edges carry both a generation and a use of the same entity.
+        for edge in &graph.edges {
+            if edge.edge_type != "data_flow" {
+                continue;
+            }
+            let entity = format!("{NS}:entity/{}", edge.data_ref);
+            generated
+                .entry(entity.clone())
+                .or_insert_with(|| format!("{NS}:node/{}", edge.source));
+            used.entry(format!("{NS}:node/{}", edge.target))
+                .or_default()
+                .push(entity);
+        }
+
+        // Fold the relations back onto their activity / entity objects.
+        for activity in &mut activities {
+            if let Some(id) = activity.get("@id").and_then(Value::as_str) {
+                if let Some(refs) = used.get(id) {
+                    let list: Vec<Value> = refs.iter().map(|e| json!({ "@id": e })).collect();
+                    activity
+                        .as_object_mut()
+                        .unwrap()
+                        .insert("prov:used".into(), Value::Array(list));
+                }
+            }
+        }
+        for (entity_id, activity) in &generated {
+            if let Some(entity) = entities.get_mut(entity_id) {
+                entity
+                    .as_object_mut()
+                    .unwrap()
+                    .insert("prov:wasGeneratedBy".into(), json!({ "@id": activity }));
+            }
+        }
+
+        let mut graph_nodes: Vec<Value> = activities;
+        graph_nodes.extend(entities.into_values());
+        graph_nodes.extend(
+            agents
+                .into_keys()
+                .map(|id| json!({ "@id": id, "@type": "prov:Agent" })),
+        );
+
+        json!({
+            "@context": {
+                "prov": "http://www.w3.org/ns/prov#",
+                "xsd": "http://www.w3.org/2001/XMLSchema#",
+                NS: format!("https://constellation.example/prov/{NS}#"),
+                "prov:startedAtTime": { "@type": "xsd:dateTime" },
+                "prov:endedAtTime": { "@type": "xsd:dateTime" },
+                "prov:used": { "@type": "@id" },
+                "prov:wasGeneratedBy": { "@type": "@id" },
+                "prov:wasAssociatedWith": { "@type": "@id" }
+            },
+            "@graph": graph_nodes,
+        })
+    }
+}
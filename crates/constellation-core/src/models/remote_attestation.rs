@@ -0,0 +1,154 @@
+//! TEE remote-attestation extension for AgentCards.
+//!
+//! An [`Agent`](super::agent::Agent) can carry [`AttestationEvidence`] proving it
+//! runs inside a trusted execution environment. A coordinator submits that
+//! evidence to the attestation endpoint declared by the card's
+//! [`SecurityScheme::Attestation`](super::security::SecurityScheme::Attestation)
+//! scheme, which returns a signed token whose claims are checked against the
+//! expected measurement policy. The resulting [`AttestationVerdict`] gates
+//! whether the agent's extended-card data may be trusted.
+
+use serde::{Deserialize, Serialize};
+
+/// Enclave evidence presented by an agent to prove its TEE launch state.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AttestationEvidence {
+    /// Base64-encoded enclave attestation report.
+    pub report: String,
+    /// The measurement the agent claims its enclave was launched with.
+    pub measurement: String,
+    /// Free-form runtime-data conduit bound into the attested claims at launch
+    /// (e.g. mounted volume content), carried opaquely end to end.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub runtime_data: Option<Vec<u8>>,
+    /// Free-form init-time-data conduit bound into the attested claims at launch
+    /// (e.g. network configuration), carried opaquely end to end.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub init_time_data: Option<Vec<u8>>,
+}
+
+/// Outcome of verifying [`AttestationEvidence`] against a measurement policy.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttestationVerdict {
+    /// Whether the attested measurement matched the expected policy.
+    pub trusted: bool,
+    /// The measurement reported in the verified token.
+    pub measurement: String,
+    /// The full set of claims returned by the attestation service.
+    pub claims: serde_json::Value,
+}
+
+impl AttestationVerdict {
+    /// Whether a coordinator may trust this agent's extended-card data and
+    /// dispatch sensitive skills to it.
+    pub fn extended_card_trusted(&self) -> bool {
+        self.trusted
+    }
+}
+
+/// Error raised while verifying remote attestation.
+#[derive(Debug)]
+pub enum AttestationError {
+    /// The card declares no attestation scheme, so there is nothing to verify.
+    NoAttestationScheme,
+    /// The agent card carries no evidence to submit.
+    MissingEvidence,
+    /// The attestation service request or response failed.
+    Service(String),
+}
+
+impl std::fmt::Display for AttestationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AttestationError::NoAttestationScheme => {
+                write!(f, "card declares no attestation security scheme")
+            }
+            AttestationError::MissingEvidence => write!(f, "card carries no attestation evidence"),
+            AttestationError::Service(msg) => write!(f, "attestation service failed: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for AttestationError {}
+
+/// The token returned by an attestation service, deserialized from its response.
+#[derive(Debug, Deserialize)]
+struct AttestationToken {
+    measurement: String,
+    #[serde(default)]
+    claims: serde_json::Value,
+}
+
+/// Submit `evidence` to `endpoint` and check the returned measurement against
+/// `expected_measurement`. Shared by the card-level entry point and tests.
+///
+/// Async, like the rest of this crate's HTTP calls (e.g.
+/// [`TokenChecker`](super::authz::TokenChecker)) — `reqwest::blocking` builds
+/// its own Tokio runtime internally, which panics when called from inside one
+/// already running, and this is reachable from `Agent::verify_attestation`.
+pub(crate) async fn verify_evidence(
+    endpoint: &str,
+    expected_measurement: &str,
+    evidence: &AttestationEvidence,
+) -> Result<AttestationVerdict, AttestationError> {
+    let client = reqwest::Client::new();
+    let response = client
+        .post(endpoint)
+        .json(evidence)
+        .send()
+        .await
+        .and_then(|resp| resp.error_for_status())
+        .map_err(|e| AttestationError::Service(e.to_string()))?;
+    let token: AttestationToken = response
+        .json()
+        .await
+        .map_err(|e| AttestationError::Service(e.to_string()))?;
+    Ok(AttestationVerdict {
+        trusted: token.measurement == expected_measurement,
+        measurement: token.measurement,
+        claims: token.claims,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extended_card_trusted_mirrors_the_trusted_flag() {
+        let trusted = AttestationVerdict {
+            trusted: true,
+            measurement: "abc123".to_string(),
+            claims: serde_json::json!({}),
+        };
+        assert!(trusted.extended_card_trusted());
+
+        let untrusted = AttestationVerdict {
+            trusted: false,
+            measurement: "abc123".to_string(),
+            claims: serde_json::json!({}),
+        };
+        assert!(!untrusted.extended_card_trusted());
+    }
+
+    #[test]
+    fn test_attestation_evidence_omits_absent_optional_fields_when_serialized() {
+        let evidence = AttestationEvidence {
+            report: "cmVwb3J0".to_string(),
+            measurement: "abc123".to_string(),
+            runtime_data: None,
+            init_time_data: None,
+        };
+        let value = serde_json::to_value(&evidence).unwrap();
+        assert!(value.get("runtime_data").is_none());
+        assert!(value.get("init_time_data").is_none());
+    }
+
+    #[test]
+    fn test_attestation_token_defaults_claims_to_null_when_absent() {
+        let json = serde_json::json!({ "measurement": "abc123" });
+        let token: AttestationToken = serde_json::from_value(json).unwrap();
+        assert_eq!(token.measurement, "abc123");
+        assert!(token.claims.is_null());
+    }
+}
@@ -0,0 +1,228 @@
+//! A2A security scheme definitions for AgentCards.
+//!
+//! These mirror the OpenAPI/A2A security-scheme model so an [`Agent`](super::agent::Agent)
+//! can declare *how* a caller authenticates, and individual
+//! [`AgentSkill`](super::agent::AgentSkill)s can require specific schemes and scopes.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::agent::SecuritySchemeType;
+
+/// Location of an API key credential in a request.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiKeyLocation {
+    /// Carried in a request header.
+    Header,
+    /// Carried as a query parameter.
+    Query,
+    /// Carried in a cookie.
+    Cookie,
+}
+
+/// OAuth2 flow endpoints and scopes.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct OAuth2Flows {
+    /// Authorization endpoint URL.
+    pub authorization_url: Option<String>,
+    /// Token endpoint URL.
+    pub token_url: Option<String>,
+    /// Refresh endpoint URL.
+    pub refresh_url: Option<String>,
+    /// Available scopes (scope name → human description).
+    #[serde(default)]
+    pub scopes: HashMap<String, String>,
+}
+
+/// A concrete security scheme, tagged by its [`SecuritySchemeType`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SecurityScheme {
+    /// API key scheme: name of the parameter and where it lives.
+    ApiKey { name: String, location: ApiKeyLocation },
+    /// HTTP authentication scheme (e.g. `bearer`) with an optional bearer format.
+    Http {
+        scheme: String,
+        bearer_format: Option<String>,
+    },
+    /// OAuth2 scheme carrying its flow endpoints and scopes.
+    Oauth2 { flows: OAuth2Flows },
+    /// OpenID Connect scheme identified by its discovery document URL.
+    OpenIdConnect { open_id_connect_url: String },
+    /// Mutual TLS scheme.
+    MutualTls,
+    /// TEE remote-attestation scheme: the service endpoint callers submit
+    /// enclave evidence to, and the measurement the enclave must report.
+    Attestation {
+        attestation_endpoint: String,
+        expected_measurement: String,
+    },
+}
+
+impl SecurityScheme {
+    /// The [`SecuritySchemeType`] tag corresponding to this scheme.
+    pub fn scheme_type(&self) -> SecuritySchemeType {
+        match self {
+            SecurityScheme::ApiKey { .. } => SecuritySchemeType::ApiKey,
+            SecurityScheme::Http { .. } => SecuritySchemeType::Http,
+            SecurityScheme::Oauth2 { .. } => SecuritySchemeType::Oauth2,
+            SecurityScheme::OpenIdConnect { .. } => SecuritySchemeType::OpenIdConnect,
+            SecurityScheme::MutualTls => SecuritySchemeType::MutualTls,
+            SecurityScheme::Attestation { .. } => SecuritySchemeType::Attestation,
+        }
+    }
+}
+
+/// Endpoints resolved from an OpenID Connect discovery document.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OpenIdConfiguration {
+    /// Authorization endpoint.
+    pub authorization_endpoint: String,
+    /// Token endpoint.
+    pub token_endpoint: String,
+    /// JWKS URI.
+    pub jwks_uri: String,
+    /// Scopes supported by the provider.
+    #[serde(default)]
+    pub scopes_supported: Vec<String>,
+}
+
+impl SecurityScheme {
+    /// For an `OpenIdConnect` scheme, fetch the discovery document at
+    /// `<open_id_connect_url>/.well-known/openid-configuration` and resolve the
+    /// concrete authorization/token/jwks endpoints. Returns `None` for other
+    /// scheme types.
+    pub fn fetch_openid_configuration(&self) -> Result<Option<OpenIdConfiguration>, SecuritySchemeError> {
+        let SecurityScheme::OpenIdConnect { open_id_connect_url } = self else {
+            return Ok(None);
+        };
+        let url = format!(
+            "{}/.well-known/openid-configuration",
+            open_id_connect_url.trim_end_matches('/')
+        );
+        let body = reqwest::blocking::get(&url)
+            .and_then(|resp| resp.error_for_status())
+            .and_then(|resp| resp.text())
+            .map_err(|e| SecuritySchemeError::Discovery(e.to_string()))?;
+        let config: OpenIdConfiguration =
+            serde_json::from_str(&body).map_err(|e| SecuritySchemeError::Discovery(e.to_string()))?;
+        Ok(Some(config))
+    }
+}
+
+/// Error returned while resolving a security scheme.
+#[derive(Debug)]
+pub enum SecuritySchemeError {
+    /// Failed to fetch or parse an OpenID discovery document.
+    Discovery(String),
+}
+
+impl std::fmt::Display for SecuritySchemeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SecuritySchemeError::Discovery(msg) => write!(f, "OIDC discovery failed: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for SecuritySchemeError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scheme_type_matches_each_variant() {
+        assert_eq!(
+            SecurityScheme::ApiKey {
+                name: "X-Api-Key".to_string(),
+                location: ApiKeyLocation::Header,
+            }
+            .scheme_type(),
+            SecuritySchemeType::ApiKey
+        );
+        assert_eq!(
+            SecurityScheme::Http {
+                scheme: "bearer".to_string(),
+                bearer_format: None,
+            }
+            .scheme_type(),
+            SecuritySchemeType::Http
+        );
+        assert_eq!(
+            SecurityScheme::Oauth2 {
+                flows: OAuth2Flows::default(),
+            }
+            .scheme_type(),
+            SecuritySchemeType::Oauth2
+        );
+        assert_eq!(
+            SecurityScheme::OpenIdConnect {
+                open_id_connect_url: "https://issuer.example".to_string(),
+            }
+            .scheme_type(),
+            SecuritySchemeType::OpenIdConnect
+        );
+        assert_eq!(SecurityScheme::MutualTls.scheme_type(), SecuritySchemeType::MutualTls);
+        assert_eq!(
+            SecurityScheme::Attestation {
+                attestation_endpoint: "https://attest.example".to_string(),
+                expected_measurement: "abc123".to_string(),
+            }
+            .scheme_type(),
+            SecuritySchemeType::Attestation
+        );
+    }
+
+    #[test]
+    fn test_fetch_openid_configuration_is_none_for_non_oidc_schemes() {
+        let schemes = [
+            SecurityScheme::ApiKey {
+                name: "X-Api-Key".to_string(),
+                location: ApiKeyLocation::Header,
+            },
+            SecurityScheme::Http {
+                scheme: "bearer".to_string(),
+                bearer_format: None,
+            },
+            SecurityScheme::Oauth2 {
+                flows: OAuth2Flows::default(),
+            },
+            SecurityScheme::MutualTls,
+            SecurityScheme::Attestation {
+                attestation_endpoint: "https://attest.example".to_string(),
+                expected_measurement: "abc123".to_string(),
+            },
+        ];
+        for scheme in schemes {
+            assert!(scheme.fetch_openid_configuration().unwrap().is_none());
+        }
+    }
+
+    #[test]
+    fn test_security_scheme_serde_roundtrips_through_its_tagged_representation() {
+        let scheme = SecurityScheme::ApiKey {
+            name: "X-Api-Key".to_string(),
+            location: ApiKeyLocation::Query,
+        };
+        let value = serde_json::to_value(&scheme).unwrap();
+        assert_eq!(value["type"], "api_key");
+        assert_eq!(value["location"], "query");
+
+        let roundtripped: SecurityScheme = serde_json::from_value(value).unwrap();
+        assert_eq!(roundtripped, scheme);
+    }
+
+    #[test]
+    fn test_open_id_configuration_defaults_missing_scopes_supported_to_empty() {
+        let json = serde_json::json!({
+            "authorization_endpoint": "https://issuer.example/authorize",
+            "token_endpoint": "https://issuer.example/token",
+            "jwks_uri": "https://issuer.example/jwks.json",
+        });
+        let config: OpenIdConfiguration = serde_json::from_value(json).unwrap();
+        assert!(config.scopes_supported.is_empty());
+    }
+}
@@ -0,0 +1,313 @@
+//! Transform (proxy) re-encryption for redirecting envelopes.
+//!
+//! An envelope encrypted to recipient A can be transformed so that agent B can
+//! read it, without exposing the plaintext to the transforming party. The
+//! content key that decrypts the envelope's ciphertext body is carried as a
+//! [`KeyEnvelope`] — itself ECIES-sealed (ephemeral X25519 ECDH + AES-256-GCM)
+//! to one recipient's public key, so only that recipient can recover it.
+//!
+//! Delegation happens in two steps. First, the delegator A — who already
+//! holds the content key, having opened their own `KeyEnvelope` — calls
+//! [`generate_transform_key`] to seal that same content key to B's public key,
+//! producing a [`TransformKey`] that bundles this precomputed
+//! [`KeyEnvelope`] for B. Second, a semi-trusted transformer later calls
+//! [`McpEncryptedMessage::transform`], which copies the ciphertext body
+//! verbatim and swaps in the precomputed envelope for B — it never handles
+//! the content key or plaintext itself, only opaque bytes A already sealed.
+
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use chrono::{DateTime, Utc};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use x25519_dalek::{PublicKey, SharedSecret, StaticSecret};
+
+use crate::models::mcp::McpEncryptedMessage;
+
+/// Length of an AES-256-GCM nonce, in bytes.
+const NONCE_LEN: usize = 12;
+
+/// A content key ECIES-sealed to one recipient: an ephemeral X25519 public
+/// key plus the key bytes, AES-256-GCM encrypted under the resulting shared
+/// secret. Only the holder of the matching secret key can recover it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyEnvelope {
+    /// Ephemeral X25519 public key used for the one-time ECDH.
+    pub ephemeral_public: [u8; 32],
+    /// The content key, AES-256-GCM encrypted under the ECDH-derived key.
+    pub wrapped_key: Vec<u8>,
+    /// Nonce used for the wrap.
+    pub nonce: [u8; NONCE_LEN],
+}
+
+/// The sealed key failed to recover, either due to a wrong secret or a
+/// tampered envelope (the AEAD tag will not verify in either case).
+#[derive(Debug, PartialEq)]
+pub struct UnwrapError;
+
+impl std::fmt::Display for UnwrapError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "key envelope authentication failed")
+    }
+}
+
+impl std::error::Error for UnwrapError {}
+
+/// Seal `content_key` to `recipient_public` via one-shot ECIES: an ephemeral
+/// X25519 key pair, an ECDH shared secret with `recipient_public`, and
+/// AES-256-GCM encryption of the key bytes under a hash of that secret.
+pub fn seal_content_key(content_key: &[u8; 32], recipient_public: &PublicKey) -> KeyEnvelope {
+    let ephemeral_secret = StaticSecret::random_from_rng(OsRng);
+    let ephemeral_public = PublicKey::from(&ephemeral_secret);
+    let shared = ephemeral_secret.diffie_hellman(recipient_public);
+
+    let mut nonce_bytes = [0u8; NONCE_LEN];
+    OsRng.fill_bytes(&mut nonce_bytes);
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&derive_key(&shared)));
+    let wrapped_key = cipher
+        .encrypt(Nonce::from_slice(&nonce_bytes), content_key.as_slice())
+        .expect("AES-256-GCM encryption of a 32-byte key cannot fail");
+
+    KeyEnvelope {
+        ephemeral_public: *ephemeral_public.as_bytes(),
+        wrapped_key,
+        nonce: nonce_bytes,
+    }
+}
+
+/// Recover the content key sealed in `envelope` using `recipient_secret`.
+pub fn open_content_key(
+    envelope: &KeyEnvelope,
+    recipient_secret: &StaticSecret,
+) -> Result<[u8; 32], UnwrapError> {
+    let ephemeral_public = PublicKey::from(envelope.ephemeral_public);
+    let shared = recipient_secret.diffie_hellman(&ephemeral_public);
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&derive_key(&shared)));
+    let plaintext = cipher
+        .decrypt(Nonce::from_slice(&envelope.nonce), envelope.wrapped_key.as_slice())
+        .map_err(|_| UnwrapError)?;
+    plaintext.try_into().map_err(|_| UnwrapError)
+}
+
+/// Derive a 32-byte AES key from an X25519 shared secret.
+fn derive_key(shared: &SharedSecret) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(b"constellation-reencrypt-v1");
+    hasher.update(shared.as_bytes());
+    hasher.finalize().into()
+}
+
+/// A one-way re-encryption key delegating from one recipient to another,
+/// carrying the content key pre-sealed for the delegatee.
+#[derive(Debug, Clone)]
+pub struct TransformKey {
+    /// Agent the envelope was originally encrypted to (delegator).
+    pub from_agent: String,
+    /// Agent the envelope is being redirected to (delegatee).
+    pub to_agent: String,
+    /// The content key, sealed to the delegatee's public key. This is what
+    /// lets the delegatee actually decrypt the transformed message.
+    pub wrapped_key_for_delegatee: KeyEnvelope,
+}
+
+/// Provenance of a single transform, recorded so audit trails and
+/// `McpSignature` can show the delegation chain.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransformProvenance {
+    /// Original sender of the envelope.
+    pub original_sender: String,
+    /// Identifier of the party that performed the transform.
+    pub transformer_id: String,
+    /// Agent the content was redirected from.
+    pub from_agent: String,
+    /// Agent the content was redirected to.
+    pub to_agent: String,
+    /// When the transform was applied.
+    pub transformed_at: DateTime<Utc>,
+}
+
+/// The result of applying a [`TransformKey`] to a message: the redirected
+/// envelope body plus the sealed content key the delegatee needs to open it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransformedMessage {
+    /// The envelope with its header redirected to the delegatee.
+    pub message: McpEncryptedMessage,
+    /// The content key, sealed to the delegatee — required to decrypt
+    /// `message.ciphertext`.
+    pub key_envelope: KeyEnvelope,
+}
+
+/// Generate a one-way transform key from `from_agent` to `to_agent`, sealing
+/// `content_key` (already known to the delegator, who holds it from opening
+/// their own [`KeyEnvelope`]) to `to_public` so the delegatee can recover it.
+pub fn generate_transform_key(
+    from_agent: &str,
+    to_agent: &str,
+    content_key: &[u8; 32],
+    to_public: &PublicKey,
+) -> TransformKey {
+    TransformKey {
+        from_agent: from_agent.to_string(),
+        to_agent: to_agent.to_string(),
+        wrapped_key_for_delegatee: seal_content_key(content_key, to_public),
+    }
+}
+
+impl McpEncryptedMessage {
+    /// Apply a [`TransformKey`], redirecting this message to the delegatee.
+    ///
+    /// The ciphertext body is copied verbatim; the header's `key_id` is
+    /// relabeled for audit, and the delegatee's sealed content key is
+    /// attached so it can actually decrypt the body. The transformer handles
+    /// only opaque bytes — it never sees the content key or plaintext.
+    pub fn transform(&self, transform_key: &TransformKey) -> TransformedMessage {
+        let new_key_id = format!("{}->{}", transform_key.from_agent, transform_key.to_agent);
+        TransformedMessage {
+            message: McpEncryptedMessage {
+                ciphertext: self.ciphertext.clone(),
+                algorithm: self.algorithm.clone(),
+                iv: self.iv.clone(),
+                key_id: new_key_id,
+            },
+            key_envelope: transform_key.wrapped_key_for_delegatee.clone(),
+        }
+    }
+
+    /// Apply a transform and record its provenance in one step.
+    pub fn transform_with_provenance(
+        &self,
+        transform_key: &TransformKey,
+        original_sender: &str,
+        transformer_id: &str,
+        now: DateTime<Utc>,
+    ) -> (TransformedMessage, TransformProvenance) {
+        let provenance = TransformProvenance {
+            original_sender: original_sender.to_string(),
+            transformer_id: transformer_id.to_string(),
+            from_agent: transform_key.from_agent.clone(),
+            to_agent: transform_key.to_agent.clone(),
+            transformed_at: now,
+        };
+        (self.transform(transform_key), provenance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Encrypt `plaintext` under `content_key`, hex-encoding the ciphertext
+    /// and IV the way the rest of the envelope does.
+    fn encrypt_body(content_key: &[u8; 32], plaintext: &[u8]) -> (String, String) {
+        let mut nonce_bytes = [0u8; NONCE_LEN];
+        OsRng.fill_bytes(&mut nonce_bytes);
+        let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(content_key));
+        let ciphertext = cipher
+            .encrypt(Nonce::from_slice(&nonce_bytes), plaintext)
+            .expect("encryption of a short test payload cannot fail");
+        (hex::encode(ciphertext), hex::encode(nonce_bytes))
+    }
+
+    /// Decrypt a hex-encoded ciphertext/IV pair under `content_key`.
+    fn decrypt_body(content_key: &[u8; 32], ciphertext_hex: &str, iv_hex: &str) -> Vec<u8> {
+        let ciphertext = hex::decode(ciphertext_hex).unwrap();
+        let nonce = hex::decode(iv_hex).unwrap();
+        let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(content_key));
+        cipher
+            .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+            .expect("decryption with the correct content key must succeed")
+    }
+
+    #[test]
+    fn test_key_envelope_round_trips_for_the_sealed_recipient() {
+        let content_key = [7u8; 32];
+        let b_secret = StaticSecret::from([2u8; 32]);
+        let b_public = PublicKey::from(&b_secret);
+
+        let envelope = seal_content_key(&content_key, &b_public);
+        let recovered = open_content_key(&envelope, &b_secret).unwrap();
+        assert_eq!(recovered, content_key);
+    }
+
+    #[test]
+    fn test_key_envelope_rejects_the_wrong_secret() {
+        let content_key = [7u8; 32];
+        let b_public = PublicKey::from(&StaticSecret::from([2u8; 32]));
+        let envelope = seal_content_key(&content_key, &b_public);
+
+        let wrong_secret = StaticSecret::from([9u8; 32]);
+        assert!(open_content_key(&envelope, &wrong_secret).is_err());
+    }
+
+    #[test]
+    fn test_transform_preserves_body_and_redirects_header() {
+        let content_key = [3u8; 32];
+        let b_public = PublicKey::from(&StaticSecret::from([2u8; 32]));
+
+        let tk = generate_transform_key("agent-a", "agent-b", &content_key, &b_public);
+        let original = McpEncryptedMessage {
+            ciphertext: "cafe".to_string(),
+            algorithm: "AES-256-GCM".to_string(),
+            iv: Some("babe".to_string()),
+            key_id: "key-for-a".to_string(),
+        };
+        let transformed = original.transform(&tk);
+
+        assert_eq!(transformed.message.ciphertext, original.ciphertext);
+        assert_eq!(transformed.message.iv, original.iv);
+        assert_ne!(transformed.message.key_id, original.key_id);
+        assert!(transformed.message.key_id.contains("agent-b"));
+    }
+
+    #[test]
+    fn test_transform_records_provenance() {
+        let content_key = [3u8; 32];
+        let b_public = PublicKey::from(&StaticSecret::from([2u8; 32]));
+        let tk = generate_transform_key("agent-a", "agent-b", &content_key, &b_public);
+
+        let original = McpEncryptedMessage {
+            ciphertext: "cafe".to_string(),
+            algorithm: "AES-256-GCM".to_string(),
+            iv: Some("babe".to_string()),
+            key_id: "key-for-a".to_string(),
+        };
+        let now = Utc::now();
+        let (_, prov) =
+            original.transform_with_provenance(&tk, "agent-origin", "proxy-1", now);
+        assert_eq!(prov.original_sender, "agent-origin");
+        assert_eq!(prov.transformer_id, "proxy-1");
+        assert_eq!(prov.to_agent, "agent-b");
+    }
+
+    /// End-to-end: A encrypts a message, delegates to B via `transform`, and
+    /// B actually decrypts the redirected envelope — the property plain
+    /// header relabeling could never provide.
+    #[test]
+    fn test_delegatee_can_decrypt_the_transformed_message() {
+        let content_key = [42u8; 32];
+        let plaintext = b"top secret agent coordination plan";
+        let (ciphertext_hex, iv_hex) = encrypt_body(&content_key, plaintext);
+        let original = McpEncryptedMessage {
+            ciphertext: ciphertext_hex,
+            algorithm: "AES-256-GCM".to_string(),
+            iv: Some(iv_hex),
+            key_id: "key-for-a".to_string(),
+        };
+
+        let b_secret = StaticSecret::from([2u8; 32]);
+        let b_public = PublicKey::from(&b_secret);
+        let tk = generate_transform_key("agent-a", "agent-b", &content_key, &b_public);
+        let transformed = original.transform(&tk);
+
+        // B recovers the content key from the sealed envelope, then decrypts
+        // the (untouched) ciphertext body with it.
+        let recovered_key = open_content_key(&transformed.key_envelope, &b_secret).unwrap();
+        let recovered_plaintext = decrypt_body(
+            &recovered_key,
+            &transformed.message.ciphertext,
+            transformed.message.iv.as_ref().unwrap(),
+        );
+        assert_eq!(recovered_plaintext, plaintext);
+    }
+}
@@ -0,0 +1,522 @@
+//! Actor-critic coordinator behind
+//! [`DecisionMakingApproach::ReinforcementLearning`](crate::models::hybrid_agent::DecisionMakingApproach::ReinforcementLearning).
+//!
+//! The enum variant declares the *intent* to learn which executor should handle
+//! each decomposed task; this module is the runtime. [`RlCoordinator`] keeps a
+//! linear softmax policy over the configured [`ExecutorConfig`]s — scoring each
+//! action by a task/executor feature vector — and a scalar linear critic that
+//! estimates expected return. Completed tasks are turned into rewards from the
+//! metrics already modeled here, buffered as `(state, action, reward, next)`
+//! transitions, and — once `min_batch_size` is reached — folded into a
+//! policy-gradient step with advantage `A = reward + γ·V(next) − V(state)` and
+//! an MSE regression of the critic toward the observed return.
+//!
+//! Two invariants hold regardless of what the policy has learned: an executor
+//! whose `domain`/`skills` cannot serve a task has its probability masked to
+//! zero, and while the buffer is below a batch the coordinator falls back to a
+//! uniform choice over the feasible executors.
+
+use std::collections::HashMap;
+
+use rand::Rng;
+
+use crate::models::hybrid_agent::{ExecutorConfig, ExecutorDomain, HybridAgentConfig};
+
+/// Number of policy features per (state, executor) action.
+const POLICY_DIM: usize = 7;
+/// Number of critic features per state.
+const CRITIC_DIM: usize = 3;
+
+/// Tunable hyperparameters for the learner.
+#[derive(Debug, Clone)]
+pub struct RlHyperparams {
+    /// Minimum transitions buffered before a learning update fires. Until then
+    /// the policy falls back to uniform random selection (mirrors relearn's
+    /// `min_batch_size`).
+    pub min_batch_size: usize,
+    /// Discount factor applied to the critic's next-state estimate.
+    pub gamma: f64,
+    /// Policy-gradient step size.
+    pub policy_lr: f64,
+    /// Critic regression step size.
+    pub critic_lr: f64,
+    /// Reward weight on task success.
+    pub w_success: f64,
+    /// Reward penalty weight on the latency ratio.
+    pub w_latency: f64,
+    /// Reward penalty weight on the cost ratio.
+    pub w_cost: f64,
+    /// Reward weight on quality score.
+    pub w_quality: f64,
+    /// Latency target (ms) the realized latency is measured against.
+    pub latency_target_ms: f64,
+    /// Cost budget (per 1K tasks) the realized cost is measured against.
+    pub budget: f64,
+}
+
+impl Default for RlHyperparams {
+    fn default() -> Self {
+        Self {
+            min_batch_size: 256,
+            gamma: 0.95,
+            policy_lr: 0.01,
+            critic_lr: 0.01,
+            w_success: 1.0,
+            w_latency: 0.3,
+            w_cost: 0.2,
+            w_quality: 0.5,
+            latency_target_ms: 1000.0,
+            budget: 1.0,
+        }
+    }
+}
+
+/// A task to be routed to an executor.
+#[derive(Debug, Clone)]
+pub struct TaskFeatures {
+    /// Domain the task belongs to; an executor must match it to be feasible.
+    pub domain: ExecutorDomain,
+    /// Skill ids the task requires; an executor must provide all of them.
+    pub required_skills: Vec<String>,
+    /// Priority level (higher is more urgent).
+    pub priority: u32,
+    /// Current in-flight load per executor id, used as a policy feature.
+    pub executor_load: HashMap<String, u32>,
+}
+
+/// Realized metrics of a completed task, turned into a scalar reward.
+#[derive(Debug, Clone)]
+pub struct TaskOutcome {
+    /// Whether the task succeeded.
+    pub success: bool,
+    /// Observed end-to-end latency in milliseconds.
+    pub latency_ms: f64,
+    /// Observed cost per 1K tasks.
+    pub cost_per_1k_tasks: f64,
+    /// Observed quality score (0.0–1.0).
+    pub quality_score: f64,
+}
+
+/// One buffered `(state, action, reward, next-state)` transition.
+#[derive(Debug, Clone)]
+struct Transition {
+    /// Policy features for each feasible action, in feasible order.
+    action_features: Vec<[f64; POLICY_DIM]>,
+    /// Index (into `action_features`) of the chosen action.
+    chosen: usize,
+    /// Scalar reward.
+    reward: f64,
+    /// Critic features of the state the action was taken in.
+    critic_state: [f64; CRITIC_DIM],
+    /// Critic features of the resulting state (zeroed for a terminal step).
+    critic_next: [f64; CRITIC_DIM],
+}
+
+/// Reinforcement-learning executor coordinator.
+pub struct RlCoordinator {
+    executors: Vec<ExecutorConfig>,
+    params: RlHyperparams,
+    /// Shared policy weights (linear softmax over action features).
+    theta: [f64; POLICY_DIM],
+    /// Critic weights (linear value estimate over state features).
+    critic: [f64; CRITIC_DIM],
+    buffer: Vec<Transition>,
+}
+
+impl RlCoordinator {
+    /// Build a coordinator from a [`HybridAgentConfig`], taking the executor set
+    /// and seeding the latency target and budget from its performance targets.
+    pub fn from_config(config: &HybridAgentConfig) -> Self {
+        let mut params = RlHyperparams {
+            latency_target_ms: config.performance_targets.latency_target_ms.max(1) as f64,
+            ..RlHyperparams::default()
+        };
+        if config.performance_targets.cost_efficiency_target > 0.0 {
+            params.budget = config.performance_targets.cost_efficiency_target;
+        }
+        Self::with_params(config.executors.clone(), params)
+    }
+
+    /// Build a coordinator over an explicit executor set and hyperparameters.
+    pub fn with_params(executors: Vec<ExecutorConfig>, params: RlHyperparams) -> Self {
+        Self {
+            executors,
+            params,
+            theta: [0.0; POLICY_DIM],
+            critic: [0.0; CRITIC_DIM],
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Whether `executor` can serve `task`: same domain and every required
+    /// skill present.
+    fn is_feasible(executor: &ExecutorConfig, task: &TaskFeatures) -> bool {
+        if executor.domain != task.domain {
+            return false;
+        }
+        task.required_skills.iter().all(|required| {
+            executor.skills.iter().any(|skill| &skill.id == required)
+        })
+    }
+
+    /// Indices of the executors that can serve `task`.
+    fn feasible_indices(&self, task: &TaskFeatures) -> Vec<usize> {
+        self.executors
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| Self::is_feasible(e, task))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Policy feature vector for routing `task` to `executor`.
+    fn policy_features(&self, task: &TaskFeatures, executor: &ExecutorConfig) -> [f64; POLICY_DIM] {
+        let priority_norm = (task.priority as f64 / 10.0).min(1.0);
+        let coverage = if task.required_skills.is_empty() {
+            1.0
+        } else {
+            let covered = task
+                .required_skills
+                .iter()
+                .filter(|r| executor.skills.iter().any(|s| &s.id == *r))
+                .count();
+            covered as f64 / task.required_skills.len() as f64
+        };
+        let load = *task.executor_load.get(&executor.id).unwrap_or(&0) as f64;
+        let load_ratio = if executor.max_concurrent_tasks > 0 {
+            load / executor.max_concurrent_tasks as f64
+        } else {
+            0.0
+        };
+        let latency_ratio = executor.performance.avg_latency_ms as f64 / self.params.latency_target_ms;
+        let cost_ratio = executor.performance.cost_per_1k_tasks / self.params.budget.max(1e-9);
+        let quality = if executor.skills.is_empty() {
+            0.0
+        } else {
+            executor.skills.iter().map(|s| s.quality_score).sum::<f64>()
+                / executor.skills.len() as f64
+        };
+        [
+            1.0,
+            priority_norm,
+            coverage,
+            load_ratio,
+            latency_ratio,
+            cost_ratio,
+            quality,
+        ]
+    }
+
+    /// Critic feature vector for a state.
+    fn critic_features(&self, task: &TaskFeatures) -> [f64; CRITIC_DIM] {
+        let priority_norm = (task.priority as f64 / 10.0).min(1.0);
+        let feasible = self.feasible_indices(task).len() as f64;
+        let feasible_norm = if self.executors.is_empty() {
+            0.0
+        } else {
+            feasible / self.executors.len() as f64
+        };
+        [1.0, priority_norm, feasible_norm]
+    }
+
+    /// Softmax probabilities over the given action feature vectors.
+    fn action_probs(&self, features: &[[f64; POLICY_DIM]]) -> Vec<f64> {
+        let logits: Vec<f64> = features.iter().map(|f| dot(&self.theta, f)).collect();
+        let max = logits.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let exps: Vec<f64> = logits.iter().map(|l| (l - max).exp()).collect();
+        let sum: f64 = exps.iter().sum();
+        if sum <= 0.0 {
+            vec![1.0 / features.len() as f64; features.len()]
+        } else {
+            exps.iter().map(|e| e / sum).collect()
+        }
+    }
+
+    /// Choose an executor for `task`, or `None` if none can serve it.
+    ///
+    /// While the replay buffer is below `min_batch_size`, selection is uniform
+    /// random over the feasible executors; afterwards it samples from the
+    /// learned softmax policy. Infeasible executors are never considered.
+    pub fn select_executor(&self, task: &TaskFeatures) -> Option<&ExecutorConfig> {
+        let feasible = self.feasible_indices(task);
+        if feasible.is_empty() {
+            return None;
+        }
+
+        let mut rng = rand::thread_rng();
+        let pick = if self.buffer.len() < self.params.min_batch_size {
+            feasible[rng.gen_range(0..feasible.len())]
+        } else {
+            let features: Vec<[f64; POLICY_DIM]> = feasible
+                .iter()
+                .map(|&i| self.policy_features(task, &self.executors[i]))
+                .collect();
+            let probs = self.action_probs(&features);
+            feasible[sample(&probs, rng.gen::<f64>())]
+        };
+        self.executors.get(pick)
+    }
+
+    /// Record the outcome of a routed task and buffer the transition. When the
+    /// buffer reaches `min_batch_size` a learning update runs and it is cleared.
+    pub fn record_outcome(
+        &mut self,
+        task: &TaskFeatures,
+        chosen_executor_id: &str,
+        outcome: &TaskOutcome,
+        next_task: Option<&TaskFeatures>,
+    ) {
+        let feasible = self.feasible_indices(task);
+        let Some(chosen) = feasible
+            .iter()
+            .position(|&i| self.executors[i].id == chosen_executor_id)
+        else {
+            // The chosen executor is not feasible for this task; nothing to learn.
+            return;
+        };
+
+        let action_features: Vec<[f64; POLICY_DIM]> = feasible
+            .iter()
+            .map(|&i| self.policy_features(task, &self.executors[i]))
+            .collect();
+        let critic_state = self.critic_features(task);
+        let critic_next = next_task
+            .map(|t| self.critic_features(t))
+            .unwrap_or([0.0; CRITIC_DIM]);
+
+        self.buffer.push(Transition {
+            action_features,
+            chosen,
+            reward: self.reward(outcome),
+            critic_state,
+            critic_next,
+        });
+
+        if self.buffer.len() >= self.params.min_batch_size {
+            self.update();
+            self.buffer.clear();
+        }
+    }
+
+    /// Scalar reward from realized task metrics.
+    fn reward(&self, outcome: &TaskOutcome) -> f64 {
+        let success = if outcome.success { 1.0 } else { 0.0 };
+        self.params.w_success * success
+            - self.params.w_latency * (outcome.latency_ms / self.params.latency_target_ms)
+            - self.params.w_cost * (outcome.cost_per_1k_tasks / self.params.budget.max(1e-9))
+            + self.params.w_quality * outcome.quality_score
+    }
+
+    /// One actor-critic pass over the buffered transitions.
+    fn update(&mut self) {
+        for transition in &self.buffer {
+            let v_state = dot(&self.critic, &transition.critic_state);
+            let v_next = dot(&self.critic, &transition.critic_next);
+            let target = transition.reward + self.params.gamma * v_next;
+            let advantage = target - v_state;
+
+            // Policy gradient: push log-prob of the taken action in the
+            // direction of the advantage. grad = phi(a) - E_p[phi].
+            let probs = self.action_probs(&transition.action_features);
+            let mut expected = [0.0; POLICY_DIM];
+            for (p, features) in probs.iter().zip(&transition.action_features) {
+                for (acc, f) in expected.iter_mut().zip(features) {
+                    *acc += p * f;
+                }
+            }
+            let chosen = &transition.action_features[transition.chosen];
+            for k in 0..POLICY_DIM {
+                self.theta[k] += self.params.policy_lr * advantage * (chosen[k] - expected[k]);
+            }
+
+            // Critic regression toward the observed return (MSE gradient step).
+            for k in 0..CRITIC_DIM {
+                self.critic[k] += self.params.critic_lr * advantage * transition.critic_state[k];
+            }
+        }
+    }
+
+    /// Number of transitions currently buffered (exposed for observability).
+    pub fn buffered(&self) -> usize {
+        self.buffer.len()
+    }
+}
+
+/// Dot product of two equal-length fixed arrays.
+fn dot<const N: usize>(a: &[f64; N], b: &[f64; N]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+/// Sample an index from `probs` (assumed normalized) using `u` in `[0, 1)`.
+fn sample(probs: &[f64], u: f64) -> usize {
+    let mut acc = 0.0;
+    for (i, p) in probs.iter().enumerate() {
+        acc += p;
+        if u < acc {
+            return i;
+        }
+    }
+    probs.len() - 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::hybrid_agent::ExecutorSkill;
+
+    fn executor(id: &str, domain: ExecutorDomain, skill_ids: &[&str]) -> ExecutorConfig {
+        let mut executor = ExecutorConfig::new(id.to_string(), domain);
+        executor.max_concurrent_tasks = 4;
+        for skill_id in skill_ids {
+            executor.skills.push(ExecutorSkill {
+                id: skill_id.to_string(),
+                name: skill_id.to_string(),
+                description: String::new(),
+                input_schema: serde_json::json!({}),
+                output_schema: serde_json::json!({}),
+                avg_execution_time_ms: 100,
+                success_rate: 1.0,
+                quality_score: 0.8,
+                deterministic: true,
+            });
+        }
+        executor
+    }
+
+    fn task(domain: ExecutorDomain, required_skills: &[&str]) -> TaskFeatures {
+        TaskFeatures {
+            domain,
+            required_skills: required_skills.iter().map(|s| s.to_string()).collect(),
+            priority: 5,
+            executor_load: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_is_feasible_requires_matching_domain_and_all_skills() {
+        let exec = executor("e1", ExecutorDomain::CodeGeneration, &["rust"]);
+        assert!(RlCoordinator::is_feasible(
+            &exec,
+            &task(ExecutorDomain::CodeGeneration, &["rust"])
+        ));
+        assert!(!RlCoordinator::is_feasible(
+            &exec,
+            &task(ExecutorDomain::DataAnalysis, &["rust"])
+        ));
+        assert!(!RlCoordinator::is_feasible(
+            &exec,
+            &task(ExecutorDomain::CodeGeneration, &["rust", "python"])
+        ));
+    }
+
+    #[test]
+    fn test_select_executor_returns_none_when_no_feasible_executor() {
+        let coordinator = RlCoordinator::with_params(
+            vec![executor("e1", ExecutorDomain::CodeGeneration, &[])],
+            RlHyperparams::default(),
+        );
+        assert!(coordinator
+            .select_executor(&task(ExecutorDomain::DataAnalysis, &[]))
+            .is_none());
+    }
+
+    #[test]
+    fn test_select_executor_never_picks_an_infeasible_executor() {
+        let coordinator = RlCoordinator::with_params(
+            vec![
+                executor("feasible", ExecutorDomain::CodeGeneration, &["rust"]),
+                executor("infeasible", ExecutorDomain::DataAnalysis, &[]),
+            ],
+            RlHyperparams {
+                min_batch_size: 1000,
+                ..RlHyperparams::default()
+            },
+        );
+        let t = task(ExecutorDomain::CodeGeneration, &["rust"]);
+        for _ in 0..20 {
+            assert_eq!(coordinator.select_executor(&t).unwrap().id, "feasible");
+        }
+    }
+
+    #[test]
+    fn test_record_outcome_ignores_an_infeasible_chosen_executor() {
+        let mut coordinator = RlCoordinator::with_params(
+            vec![executor("e1", ExecutorDomain::CodeGeneration, &[])],
+            RlHyperparams {
+                min_batch_size: 1,
+                ..RlHyperparams::default()
+            },
+        );
+        let t = task(ExecutorDomain::CodeGeneration, &[]);
+        let outcome = TaskOutcome {
+            success: true,
+            latency_ms: 500.0,
+            cost_per_1k_tasks: 0.5,
+            quality_score: 0.9,
+        };
+        coordinator.record_outcome(&t, "does-not-exist", &outcome, None);
+        assert_eq!(coordinator.buffered(), 0);
+        assert_eq!(coordinator.theta, [0.0; POLICY_DIM]);
+    }
+
+    #[test]
+    fn test_record_outcome_runs_a_policy_and_critic_update_at_batch_size() {
+        let good = executor("good", ExecutorDomain::CodeGeneration, &["rust"]);
+        let mut bad = executor("bad", ExecutorDomain::CodeGeneration, &["rust"]);
+        // Give the two actions distinct features; with identical features the
+        // softmax gradient's (chosen - expected) term is exactly zero and the
+        // update would be a no-op regardless of whether it ran.
+        bad.performance.avg_latency_ms = 5000;
+
+        let mut coordinator = RlCoordinator::with_params(
+            vec![good, bad],
+            RlHyperparams {
+                min_batch_size: 1,
+                ..RlHyperparams::default()
+            },
+        );
+        let t = task(ExecutorDomain::CodeGeneration, &["rust"]);
+        let outcome = TaskOutcome {
+            success: true,
+            latency_ms: 200.0,
+            cost_per_1k_tasks: 0.2,
+            quality_score: 1.0,
+        };
+
+        assert_eq!(coordinator.theta, [0.0; POLICY_DIM]);
+        assert_eq!(coordinator.critic, [0.0; CRITIC_DIM]);
+
+        coordinator.record_outcome(&t, "good", &outcome, None);
+
+        // Reaching min_batch_size fires an update and clears the buffer.
+        assert_eq!(coordinator.buffered(), 0);
+        assert_ne!(coordinator.theta, [0.0; POLICY_DIM]);
+        assert_ne!(coordinator.critic, [0.0; CRITIC_DIM]);
+    }
+
+    #[test]
+    fn test_reward_rewards_success_and_quality_penalizes_latency_and_cost() {
+        let coordinator = RlCoordinator::with_params(vec![], RlHyperparams::default());
+        let good = TaskOutcome {
+            success: true,
+            latency_ms: 0.0,
+            cost_per_1k_tasks: 0.0,
+            quality_score: 1.0,
+        };
+        let bad = TaskOutcome {
+            success: false,
+            latency_ms: 5000.0,
+            cost_per_1k_tasks: 5.0,
+            quality_score: 0.0,
+        };
+        assert!(coordinator.reward(&good) > coordinator.reward(&bad));
+    }
+
+    #[test]
+    fn test_sample_picks_bucket_containing_u() {
+        let probs = vec![0.2, 0.3, 0.5];
+        assert_eq!(sample(&probs, 0.0), 0);
+        assert_eq!(sample(&probs, 0.25), 1);
+        assert_eq!(sample(&probs, 0.9), 2);
+    }
+}
@@ -0,0 +1,239 @@
+//! Key rotation engine enforcing [`RotationPolicy`](crate::models::mcp::RotationPolicy).
+//!
+//! The policy declares *when* keys should rotate; this module acts on it. The
+//! [`KeyRotationManager`] tracks per-key creation timestamps, generates fresh
+//! Ed25519 keypairs, re-points the active signing key, and keeps a short grace
+//! window during which a retired key still verifies inbound messages but no
+//! longer signs. Retired secret material is wrapped in [`Zeroizing`] so it is
+//! wiped from memory on drop.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, Utc};
+use ed25519_dalek::{SigningKey, VerifyingKey};
+use rand::rngs::OsRng;
+use zeroize::Zeroizing;
+
+use crate::models::mcp::RotationPolicy;
+
+/// Error returned by rotation operations.
+#[derive(Debug, PartialEq)]
+pub enum RotationError {
+    /// No active key is available to sign with.
+    NoActiveKey,
+    /// The active key has exceeded `max_lifetime_days` and may not sign.
+    KeyExpired { key_id: String },
+    /// The referenced key is unknown to the manager.
+    UnknownKey { key_id: String },
+}
+
+impl std::fmt::Display for RotationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RotationError::NoActiveKey => write!(f, "no active signing key"),
+            RotationError::KeyExpired { key_id } => {
+                write!(f, "key {key_id} exceeded max lifetime and may not sign")
+            }
+            RotationError::UnknownKey { key_id } => write!(f, "unknown key {key_id}"),
+        }
+    }
+}
+
+impl std::error::Error for RotationError {}
+
+/// A managed keypair with provenance needed to enforce rotation rules.
+struct ManagedKey {
+    /// Secret signing bytes, zeroized on drop.
+    signing_bytes: Zeroizing<[u8; 32]>,
+    verifying_key: VerifyingKey,
+    created_at: DateTime<Utc>,
+    /// When set, the key is retired and only verifies until this instant.
+    retires_at: Option<DateTime<Utc>>,
+    compromised: bool,
+}
+
+impl ManagedKey {
+    fn signing_key(&self) -> SigningKey {
+        SigningKey::from_bytes(&self.signing_bytes)
+    }
+}
+
+/// Manages the lifecycle of signing keys under a [`RotationPolicy`].
+pub struct KeyRotationManager {
+    policy: RotationPolicy,
+    keys: HashMap<String, ManagedKey>,
+    active_key_id: Option<String>,
+    /// How long a retired key keeps verifying inbound messages.
+    grace_window: Duration,
+    next_index: u64,
+}
+
+impl KeyRotationManager {
+    /// Create a manager for `policy` with a one-hour verification grace window.
+    pub fn new(policy: RotationPolicy) -> Self {
+        Self {
+            policy,
+            keys: HashMap::new(),
+            active_key_id: None,
+            grace_window: Duration::hours(1),
+            next_index: 0,
+        }
+    }
+
+    /// Override the grace window during which retired keys still verify.
+    pub fn with_grace_window(mut self, grace: Duration) -> Self {
+        self.grace_window = grace;
+        self
+    }
+
+    /// Generate a fresh keypair, install it as the active signing key, and
+    /// return its key id. Uses the current time as the creation timestamp.
+    pub fn generate_active_key(&mut self, now: DateTime<Utc>) -> String {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let verifying_key = signing_key.verifying_key();
+        let signing_bytes = Zeroizing::new(signing_key.to_bytes());
+
+        let key_id = format!("key-{}", self.next_index);
+        self.next_index += 1;
+
+        self.keys.insert(
+            key_id.clone(),
+            ManagedKey {
+                signing_bytes,
+                verifying_key,
+                created_at: now,
+                retires_at: None,
+                compromised: false,
+            },
+        );
+        self.active_key_id = Some(key_id.clone());
+        key_id
+    }
+
+    /// The id of the current active signing key, if any.
+    pub fn active_key_id(&self) -> Option<&str> {
+        self.active_key_id.as_deref()
+    }
+
+    /// Key ids whose age at `now` exceeds `policy.interval_days`, or that have
+    /// been marked compromised.
+    pub fn keys_due_for_rotation(&self, now: DateTime<Utc>) -> Vec<String> {
+        let interval = Duration::days(self.policy.interval_days as i64);
+        self.keys
+            .iter()
+            .filter(|(_, key)| key.compromised || now - key.created_at >= interval)
+            .map(|(id, _)| id.clone())
+            .collect()
+    }
+
+    /// Rotate the active key: generate a replacement and retire the old key into
+    /// the grace window so it still verifies but no longer signs.
+    pub fn rotate(&mut self, now: DateTime<Utc>) -> String {
+        if let Some(old_id) = self.active_key_id.clone() {
+            if let Some(old) = self.keys.get_mut(&old_id) {
+                old.retires_at = Some(now + self.grace_window);
+            }
+        }
+        self.generate_active_key(now)
+    }
+
+    /// Force immediate rotation of a key. When `policy.rotate_on_compromise` is
+    /// set and the key is the active one, a replacement is generated at once.
+    pub fn mark_compromised(&mut self, key_id: &str, now: DateTime<Utc>) -> Result<(), RotationError> {
+        let key = self
+            .keys
+            .get_mut(key_id)
+            .ok_or_else(|| RotationError::UnknownKey {
+                key_id: key_id.to_string(),
+            })?;
+        key.compromised = true;
+        // Compromised keys must not verify either; collapse the grace window.
+        key.retires_at = Some(now);
+
+        if self.policy.rotate_on_compromise && self.active_key_id.as_deref() == Some(key_id) {
+            self.rotate(now);
+        }
+        Ok(())
+    }
+
+    /// Return the active signing key, refusing if it is expired past
+    /// `max_lifetime_days` or compromised.
+    pub fn signing_key(&self, now: DateTime<Utc>) -> Result<(String, SigningKey), RotationError> {
+        let key_id = self.active_key_id.clone().ok_or(RotationError::NoActiveKey)?;
+        let key = self
+            .keys
+            .get(&key_id)
+            .ok_or_else(|| RotationError::UnknownKey { key_id: key_id.clone() })?;
+
+        let max_lifetime = Duration::days(self.policy.max_lifetime_days as i64);
+        if key.compromised || now - key.created_at >= max_lifetime {
+            return Err(RotationError::KeyExpired { key_id });
+        }
+        Ok((key_id, key.signing_key()))
+    }
+
+    /// Return a verifying key usable for inbound messages: active keys and keys
+    /// still inside their grace window verify; fully retired keys do not.
+    pub fn verifying_key(&self, key_id: &str, now: DateTime<Utc>) -> Option<VerifyingKey> {
+        let key = self.keys.get(key_id)?;
+        match key.retires_at {
+            Some(retires_at) if now >= retires_at => None,
+            _ => Some(key.verifying_key),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> RotationPolicy {
+        RotationPolicy {
+            interval_days: 90,
+            rotate_on_compromise: true,
+            max_lifetime_days: 365,
+        }
+    }
+
+    #[test]
+    fn test_keys_due_for_rotation_by_age() {
+        let mut mgr = KeyRotationManager::new(policy());
+        let created = Utc::now() - Duration::days(100);
+        let id = mgr.generate_active_key(created);
+        assert_eq!(mgr.keys_due_for_rotation(Utc::now()), vec![id]);
+    }
+
+    #[test]
+    fn test_rotate_keeps_old_key_verifying_within_grace() {
+        let mut mgr = KeyRotationManager::new(policy());
+        let now = Utc::now();
+        let old = mgr.generate_active_key(now);
+        let new = mgr.rotate(now);
+
+        assert_ne!(old, new);
+        assert!(mgr.verifying_key(&old, now).is_some());
+        assert!(mgr
+            .verifying_key(&old, now + Duration::hours(2))
+            .is_none());
+    }
+
+    #[test]
+    fn test_mark_compromised_forces_rotation() {
+        let mut mgr = KeyRotationManager::new(policy());
+        let now = Utc::now();
+        let old = mgr.generate_active_key(now);
+        mgr.mark_compromised(&old, now).unwrap();
+        assert_ne!(mgr.active_key_id(), Some(old.as_str()));
+    }
+
+    #[test]
+    fn test_signing_refused_past_max_lifetime() {
+        let mut mgr = KeyRotationManager::new(policy());
+        let created = Utc::now() - Duration::days(400);
+        mgr.generate_active_key(created);
+        assert!(matches!(
+            mgr.signing_key(Utc::now()),
+            Err(RotationError::KeyExpired { .. })
+        ));
+    }
+}
@@ -0,0 +1,283 @@
+//! Topological execution scheduler for a [`DataTransformationGraph`].
+//!
+//! The DTG models dependencies but offers no way to drive execution in order.
+//! [`DtgScheduler`] performs a Kahn-style topological traversal: it seeds a
+//! ready set with the zero-in-degree nodes and, on each step, yields the nodes
+//! whose dependencies are satisfied so independent transformations can run
+//! concurrently. An executor loops [`next_ready`](DtgScheduler::next_ready) /
+//! [`mark_node_done`](DtgScheduler::mark_node_done) until the graph reaches a
+//! terminal status.
+//!
+//! Edges come in two strengths. A *strong* edge must reach
+//! [`DtgNodeStatus::Completed`] before the target is runnable; a *weak*
+//! (`optional`) edge does not block — when it ends up `Failed`/`Cancelled` the
+//! carried data ref is surfaced as a missing input via
+//! [`missing_weak_inputs`](DtgScheduler::missing_weak_inputs) rather than
+//! stalling the node.
+
+use std::collections::HashSet;
+
+use uuid::Uuid;
+
+use crate::models::dtg::{DataTransformationGraph, DtgEdge, DtgNodeStatus};
+
+/// Error returned by the scheduler.
+#[derive(Debug, PartialEq)]
+pub enum SchedulerError {
+    /// The graph contains a cycle and cannot be topologically ordered.
+    Cyclic,
+}
+
+impl std::fmt::Display for SchedulerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SchedulerError::Cyclic => write!(f, "graph is not acyclic"),
+        }
+    }
+}
+
+impl std::error::Error for SchedulerError {}
+
+/// Drives a DTG forward in dependency order.
+#[derive(Debug, Default)]
+pub struct DtgScheduler {
+    /// Nodes already handed out by [`next_ready`], so a node is not dispatched
+    /// twice while it is still executing.
+    dispatched: HashSet<Uuid>,
+    /// Set once the graph has been confirmed acyclic.
+    validated: bool,
+}
+
+/// Whether an edge is a weak (optional) dependency.
+fn is_weak(edge: &DtgEdge) -> bool {
+    edge.optional || edge.edge_type == "weak"
+}
+
+/// Whether a status is terminal (will not change without re-execution).
+fn is_terminal(status: &DtgNodeStatus) -> bool {
+    matches!(
+        status,
+        DtgNodeStatus::Completed | DtgNodeStatus::Failed | DtgNodeStatus::Cancelled
+    )
+}
+
+impl DtgScheduler {
+    /// Create a scheduler over a fresh graph.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return the next batch of runnable nodes: those not yet dispatched whose
+    /// strong dependencies are all `Completed` and whose weak dependencies have
+    /// all reached a terminal status. Errors if the graph is not acyclic.
+    pub fn next_ready(
+        &mut self,
+        graph: &DataTransformationGraph,
+    ) -> Result<Vec<Uuid>, SchedulerError> {
+        if !self.validated {
+            if !graph.is_acyclic() {
+                return Err(SchedulerError::Cyclic);
+            }
+            self.validated = true;
+        }
+
+        let mut ready = Vec::new();
+        for (id, node) in &graph.nodes {
+            if self.dispatched.contains(id) || is_terminal(&node.status) {
+                continue;
+            }
+            if self.is_runnable(graph, *id) {
+                ready.push(*id);
+            }
+        }
+
+        for id in &ready {
+            self.dispatched.insert(*id);
+        }
+        Ok(ready)
+    }
+
+    /// A node is runnable when every strong dependency is `Completed` and every
+    /// weak dependency has resolved to a terminal status.
+    fn is_runnable(&self, graph: &DataTransformationGraph, node_id: Uuid) -> bool {
+        graph.edges.iter().filter(|e| e.target == node_id).all(|edge| {
+            let Some(dep) = graph.nodes.get(&edge.source) else {
+                return true;
+            };
+            if is_weak(edge) {
+                is_terminal(&dep.status)
+            } else {
+                matches!(dep.status, DtgNodeStatus::Completed)
+            }
+        })
+    }
+
+    /// Weak-dependency data refs for `node_id` whose source `Failed`/`Cancelled`,
+    /// i.e. inputs the node will have to proceed without.
+    pub fn missing_weak_inputs(
+        &self,
+        graph: &DataTransformationGraph,
+        node_id: Uuid,
+    ) -> Vec<Uuid> {
+        graph
+            .edges
+            .iter()
+            .filter(|e| e.target == node_id && is_weak(e))
+            .filter(|e| {
+                graph
+                    .nodes
+                    .get(&e.source)
+                    .map(|n| matches!(n.status, DtgNodeStatus::Failed | DtgNodeStatus::Cancelled))
+                    .unwrap_or(false)
+            })
+            .map(|e| e.data_ref)
+            .collect()
+    }
+
+    /// Record the terminal `status` of a dispatched node and fold the graph's
+    /// overall status forward once every node has settled.
+    pub fn mark_node_done(
+        &mut self,
+        graph: &mut DataTransformationGraph,
+        node_id: Uuid,
+        status: DtgNodeStatus,
+    ) {
+        if let Some(node) = graph.nodes.get_mut(&node_id) {
+            node.status = status;
+        }
+        self.dispatched.remove(&node_id);
+
+        if graph.nodes.values().all(|n| is_terminal(&n.status)) {
+            let any_failed = graph
+                .nodes
+                .values()
+                .any(|n| matches!(n.status, DtgNodeStatus::Failed | DtgNodeStatus::Cancelled));
+            graph.status = if any_failed {
+                crate::models::dtg::DtgGraphStatus::PartiallyCompleted
+            } else {
+                crate::models::dtg::DtgGraphStatus::Completed
+            };
+        }
+    }
+
+    /// Whether every node has reached a terminal status.
+    pub fn is_complete(&self, graph: &DataTransformationGraph) -> bool {
+        graph.nodes.values().all(|n| is_terminal(&n.status))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::dtg::{DtgGraphStatus, DtgNode};
+
+    fn node(graph: &mut DataTransformationGraph) -> Uuid {
+        graph.add_node(DtgNode::new("skill".to_string(), "agent".to_string()))
+    }
+
+    fn data_ref() -> Uuid {
+        Uuid::new_v4()
+    }
+
+    #[test]
+    fn test_next_ready_returns_cyclic_error_for_a_cycle() {
+        let mut graph = DataTransformationGraph::new("cyclic".to_string());
+        let a = node(&mut graph);
+        let b = node(&mut graph);
+        graph.add_edge(a, b, data_ref(), "data_flow".to_string());
+        graph.add_edge(b, a, data_ref(), "data_flow".to_string());
+
+        let mut scheduler = DtgScheduler::new();
+        assert_eq!(scheduler.next_ready(&graph), Err(SchedulerError::Cyclic));
+    }
+
+    #[test]
+    fn test_next_ready_dispatches_a_node_only_once() {
+        let mut graph = DataTransformationGraph::new("linear".to_string());
+        let a = node(&mut graph);
+
+        let mut scheduler = DtgScheduler::new();
+        assert_eq!(scheduler.next_ready(&graph).unwrap(), vec![a]);
+        assert_eq!(scheduler.next_ready(&graph).unwrap(), Vec::<Uuid>::new());
+    }
+
+    #[test]
+    fn test_next_ready_blocks_on_an_incomplete_strong_dependency() {
+        let mut graph = DataTransformationGraph::new("strong".to_string());
+        let upstream = node(&mut graph);
+        let downstream = node(&mut graph);
+        graph.add_edge(upstream, downstream, data_ref(), "data_flow".to_string());
+
+        let mut scheduler = DtgScheduler::new();
+        assert_eq!(scheduler.next_ready(&graph).unwrap(), vec![upstream]);
+
+        // Downstream is not yet runnable: its strong dependency is still pending.
+        assert_eq!(scheduler.next_ready(&graph).unwrap(), Vec::<Uuid>::new());
+
+        graph.nodes.get_mut(&upstream).unwrap().status = DtgNodeStatus::Completed;
+        assert_eq!(scheduler.next_ready(&graph).unwrap(), vec![downstream]);
+    }
+
+    #[test]
+    fn test_next_ready_lets_a_weak_dependency_proceed_once_failed() {
+        let mut graph = DataTransformationGraph::new("weak".to_string());
+        let upstream = node(&mut graph);
+        let downstream = node(&mut graph);
+        let dep_data = data_ref();
+        graph.add_weak_edge(upstream, downstream, dep_data);
+
+        let mut scheduler = DtgScheduler::new();
+        scheduler.next_ready(&graph).unwrap();
+
+        // A weak dependency that is still pending does not unblock the target.
+        assert_eq!(scheduler.next_ready(&graph).unwrap(), Vec::<Uuid>::new());
+
+        graph.nodes.get_mut(&upstream).unwrap().status = DtgNodeStatus::Failed;
+        assert_eq!(scheduler.next_ready(&graph).unwrap(), vec![downstream]);
+        assert_eq!(
+            scheduler.missing_weak_inputs(&graph, downstream),
+            vec![dep_data]
+        );
+    }
+
+    #[test]
+    fn test_missing_weak_inputs_is_empty_when_the_dependency_completed() {
+        let mut graph = DataTransformationGraph::new("weak-ok".to_string());
+        let upstream = node(&mut graph);
+        let downstream = node(&mut graph);
+        graph.add_weak_edge(upstream, downstream, data_ref());
+        graph.nodes.get_mut(&upstream).unwrap().status = DtgNodeStatus::Completed;
+
+        let scheduler = DtgScheduler::new();
+        assert!(scheduler.missing_weak_inputs(&graph, downstream).is_empty());
+    }
+
+    #[test]
+    fn test_mark_node_done_folds_graph_status_to_completed() {
+        let mut graph = DataTransformationGraph::new("all-ok".to_string());
+        let a = node(&mut graph);
+        let b = node(&mut graph);
+
+        let mut scheduler = DtgScheduler::new();
+        scheduler.next_ready(&graph).unwrap();
+        scheduler.mark_node_done(&mut graph, a, DtgNodeStatus::Completed);
+        assert!(!scheduler.is_complete(&graph));
+        scheduler.mark_node_done(&mut graph, b, DtgNodeStatus::Completed);
+
+        assert!(scheduler.is_complete(&graph));
+        assert!(matches!(graph.status, DtgGraphStatus::Completed));
+    }
+
+    #[test]
+    fn test_mark_node_done_folds_graph_status_to_partially_completed_on_failure() {
+        let mut graph = DataTransformationGraph::new("one-fails".to_string());
+        let a = node(&mut graph);
+        let b = node(&mut graph);
+
+        let mut scheduler = DtgScheduler::new();
+        scheduler.mark_node_done(&mut graph, a, DtgNodeStatus::Completed);
+        scheduler.mark_node_done(&mut graph, b, DtgNodeStatus::Failed);
+
+        assert!(matches!(graph.status, DtgGraphStatus::PartiallyCompleted));
+    }
+}
@@ -0,0 +1,439 @@
+//! Opt-in OpenTelemetry instrumentation for agent lifecycle and skill execution.
+//!
+//! When [`Telemetry::init`] succeeds it stands up a single OTLP pipeline
+//! (traces + metrics + logs) configured from the standard `OTEL_EXPORTER_OTLP_*`
+//! environment variables. Every span and metric carries the emitting [`Agent`]'s
+//! identity (`id`, `name`, `version`, `provider.name`) and its
+//! [`ConstellationMetadata`] (`role`, `internal_status`) as OTEL resource
+//! attributes, so a whole fleet is attributable in any OTEL-compatible backend.
+//!
+//! Skill invocations are wrapped in spans named after the skill id; per-skill
+//! counters and duration histograms are recorded, and a gauge tracks the
+//! agent's `internal_status` so `idle`/`active` transitions are observable.
+
+use std::time::Instant;
+
+use opentelemetry::metrics::{Counter, Histogram, Meter};
+use opentelemetry::{global, KeyValue};
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::metrics::SdkMeterProvider;
+use opentelemetry_sdk::trace::Tracer;
+use opentelemetry_sdk::{runtime, Resource};
+
+use crate::models::agent::Agent;
+use crate::models::dtg::DataTransformationGraph;
+use crate::models::hybrid_agent::HybridAgentConfig;
+
+/// Error raised while building the OTLP telemetry pipeline.
+#[derive(Debug)]
+pub struct TelemetryError(pub String);
+
+impl std::fmt::Display for TelemetryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "telemetry error: {}", self.0)
+    }
+}
+
+impl std::error::Error for TelemetryError {}
+
+impl From<opentelemetry::trace::TraceError> for TelemetryError {
+    fn from(e: opentelemetry::trace::TraceError) -> Self {
+        TelemetryError(e.to_string())
+    }
+}
+
+impl From<opentelemetry::metrics::MetricsError> for TelemetryError {
+    fn from(e: opentelemetry::metrics::MetricsError) -> Self {
+        TelemetryError(e.to_string())
+    }
+}
+
+/// Build the OTEL [`Resource`] describing the emitting agent. The attribute
+/// keys follow OTEL semantic conventions (`service.*`) where they fit, and fall
+/// back to `constellation.*` for platform-specific fields.
+fn agent_resource(agent: &Agent) -> Resource {
+    let mut attrs = vec![
+        KeyValue::new("service.name", agent.name.clone()),
+        KeyValue::new("service.version", agent.version.clone()),
+        KeyValue::new("service.instance.id", agent.id.clone()),
+        KeyValue::new("service.namespace", agent.provider.name.clone()),
+    ];
+    if let Some(metadata) = &agent.metadata {
+        let constellation = metadata.get("constellation");
+        if let Some(role) = constellation.and_then(|c| c.get("role")).and_then(|v| v.as_str()) {
+            attrs.push(KeyValue::new("constellation.role", role.to_string()));
+        }
+        if let Some(status) = constellation
+            .and_then(|c| c.get("internal_status"))
+            .and_then(|v| v.as_str())
+        {
+            attrs.push(KeyValue::new("constellation.internal_status", status.to_string()));
+        }
+    }
+    Resource::new(attrs)
+}
+
+/// A live telemetry pipeline tied to one agent. Dropping or calling
+/// [`shutdown`](Telemetry::shutdown) flushes and tears down the exporters.
+pub struct Telemetry {
+    tracer: Tracer,
+    meter_provider: SdkMeterProvider,
+    invocations: Counter<u64>,
+    durations: Histogram<f64>,
+    status: Histogram<u64>,
+    node_latency: Histogram<u64>,
+    node_quality: Histogram<f64>,
+    node_retries: Counter<u64>,
+    executor_throughput: Histogram<f64>,
+    executor_cost: Histogram<f64>,
+}
+
+impl Telemetry {
+    /// Initialize a single OTLP pipeline for `agent`, reading endpoint and
+    /// protocol from the standard `OTEL_EXPORTER_OTLP_*` environment variables.
+    pub fn init(agent: &Agent) -> Result<Self, TelemetryError> {
+        let resource = agent_resource(agent);
+
+        let tracer = opentelemetry_otlp::new_pipeline()
+            .tracing()
+            .with_exporter(opentelemetry_otlp::new_exporter().tonic().with_env())
+            .with_trace_config(
+                opentelemetry_sdk::trace::Config::default().with_resource(resource.clone()),
+            )
+            .install_batch(runtime::Tokio)?;
+
+        let meter_provider = opentelemetry_otlp::new_pipeline()
+            .metrics(runtime::Tokio)
+            .with_exporter(opentelemetry_otlp::new_exporter().tonic().with_env())
+            .with_resource(resource)
+            .build()?;
+        global::set_meter_provider(meter_provider.clone());
+
+        let meter = global::meter("constellation");
+        let telemetry = Self::from_parts(tracer, meter_provider, &meter);
+        Ok(telemetry)
+    }
+
+    fn from_parts(tracer: Tracer, meter_provider: SdkMeterProvider, meter: &Meter) -> Self {
+        let invocations = meter
+            .u64_counter("constellation.skill.invocations")
+            .with_description("Number of skill invocations per skill")
+            .init();
+        let durations = meter
+            .f64_histogram("constellation.skill.duration")
+            .with_description("Skill invocation duration in seconds")
+            .with_unit("s")
+            .init();
+        let status = meter
+            .u64_histogram("constellation.agent.status")
+            .with_description("Agent internal status transitions (1 = active, 0 = idle)")
+            .init();
+        let node_latency = meter
+            .u64_histogram("constellation.dtg.node.cpu_time")
+            .with_description("DTG node CPU time in milliseconds")
+            .with_unit("ms")
+            .init();
+        let node_quality = meter
+            .f64_histogram("constellation.dtg.node.quality")
+            .with_description("DTG node quality score (0.0 to 1.0)")
+            .init();
+        let node_retries = meter
+            .u64_counter("constellation.dtg.node.retries")
+            .with_description("DTG node retry attempts")
+            .init();
+        let executor_throughput = meter
+            .f64_histogram("constellation.executor.throughput")
+            .with_description("Executor throughput in tasks per second")
+            .init();
+        let executor_cost = meter
+            .f64_histogram("constellation.executor.cost_per_1k_tasks")
+            .with_description("Executor cost per 1K tasks")
+            .init();
+        Self {
+            tracer,
+            meter_provider,
+            invocations,
+            durations,
+            status,
+            node_latency,
+            node_quality,
+            node_retries,
+            executor_throughput,
+            executor_cost,
+        }
+    }
+
+    /// Run `f` inside a span named after `skill_id`, recording an invocation
+    /// counter and a duration histogram tagged with the skill id.
+    pub fn instrument_skill<T>(&self, skill_id: &str, f: impl FnOnce() -> T) -> T {
+        use opentelemetry::trace::{Span, TraceContextExt, Tracer as _};
+        let skill_attr = [KeyValue::new("skill.id", skill_id.to_string())];
+        self.invocations.add(1, &skill_attr);
+
+        let span = self.tracer.start(skill_id.to_string());
+        let cx = opentelemetry::Context::current_with_span(span);
+        let _guard = cx.clone().attach();
+
+        let started = Instant::now();
+        let result = f();
+        let elapsed = started.elapsed().as_secs_f64();
+
+        self.durations.record(elapsed, &skill_attr);
+        cx.span().end();
+        result
+    }
+
+    /// Open a span for `graph` with a child span per node, attaching each
+    /// node's `skill_id`, `agent_id`, and final [`DtgMetrics`] as span
+    /// attributes, and recording latency/quality histograms and a retry counter.
+    pub fn instrument_dtg(&self, graph: &DataTransformationGraph) {
+        use opentelemetry::trace::{Span, TraceContextExt, Tracer as _};
+        let mut graph_span = self.tracer.start(format!("dtg:{}", graph.name));
+        graph_span.set_attribute(KeyValue::new("dtg.id", graph.id.to_string()));
+        let cx = opentelemetry::Context::current_with_span(graph_span);
+
+        for node in graph.nodes.values() {
+            let attrs = [
+                KeyValue::new("skill.id", node.skill_id.clone()),
+                KeyValue::new("agent.id", node.agent_id.clone()),
+            ];
+            let mut span = self.tracer.start_with_context(node.skill_id.clone(), &cx);
+            span.set_attribute(KeyValue::new("skill.id", node.skill_id.clone()));
+            span.set_attribute(KeyValue::new("agent.id", node.agent_id.clone()));
+            span.set_attribute(KeyValue::new(
+                "dtg.node.cpu_time_ms",
+                node.metrics.cpu_time_ms as i64,
+            ));
+            span.set_attribute(KeyValue::new(
+                "dtg.node.memory_bytes",
+                node.metrics.memory_bytes as i64,
+            ));
+            span.set_attribute(KeyValue::new(
+                "dtg.node.network_bytes",
+                node.metrics.network_bytes as i64,
+            ));
+
+            self.node_latency.record(node.metrics.cpu_time_ms, &attrs);
+            self.node_quality.record(node.metrics.quality_score, &attrs);
+            self.node_retries
+                .add(node.metrics.retry_count as u64, &attrs);
+            span.end();
+        }
+        cx.span().end();
+    }
+
+    /// Emit per-executor throughput and cost metrics for a hybrid agent,
+    /// tagged by executor `id` and `domain`, so measured performance can be
+    /// compared against the configured `PerformanceTargets`.
+    pub fn record_executor_metrics(&self, config: &HybridAgentConfig) {
+        for executor in &config.executors {
+            let attrs = [
+                KeyValue::new("executor.id", executor.id.clone()),
+                KeyValue::new("executor.domain", format!("{:?}", executor.domain)),
+            ];
+            self.executor_throughput
+                .record(executor.performance.throughput_tps, &attrs);
+            self.executor_cost
+                .record(executor.performance.cost_per_1k_tasks, &attrs);
+        }
+    }
+
+    /// Update the status gauge from an agent's `internal_status`, treating any
+    /// status other than `idle` as active. `last_seen` is attached for context.
+    pub fn record_status(&self, internal_status: &str, last_seen: Option<&str>) {
+        let value = if internal_status == "idle" { 0 } else { 1 };
+        let mut attrs = vec![KeyValue::new("internal_status", internal_status.to_string())];
+        if let Some(last_seen) = last_seen {
+            attrs.push(KeyValue::new("last_seen", last_seen.to_string()));
+        }
+        self.status.record(value, &attrs);
+    }
+
+    /// Flush and shut down the telemetry pipeline.
+    pub fn shutdown(self) {
+        global::shutdown_tracer_provider();
+        let _ = self.meter_provider.shutdown();
+    }
+}
+
+impl DataTransformationGraph {
+    /// Export this graph as a single OpenTelemetry trace tree using `tracer`.
+    ///
+    /// Each [`DtgNode`](crate::models::dtg::DtgNode) becomes a span parented by
+    /// its first dependency (additional dependencies become span links), so the
+    /// span tree mirrors the data-flow topology surfaced by
+    /// [`get_dependencies`](DataTransformationGraph::get_dependencies) /
+    /// [`get_dependents`](DataTransformationGraph::get_dependents). `skill_id`
+    /// and `agent_id` map to span attributes, `error` sets the span status, and
+    /// the per-node resource counters are emitted as a `metrics` span event, so
+    /// a multi-agent run can be inspected in any OTEL-compatible backend.
+    pub fn export_spans(&self, tracer: &Tracer) {
+        use opentelemetry::trace::{
+            Link, Span, SpanBuilder, Status, TraceContextExt, Tracer as _,
+        };
+
+        let root = tracer.start(format!("dtg:{}", self.name));
+        let root_cx = opentelemetry::Context::current_with_span(root);
+        root_cx
+            .span()
+            .set_attribute(KeyValue::new("dtg.id", self.id.to_string()));
+
+        // Parent contexts keyed by node, filled in topological order so a span
+        // can always parent itself under an already-open dependency span.
+        let mut contexts: std::collections::HashMap<uuid::Uuid, opentelemetry::Context> =
+            std::collections::HashMap::new();
+
+        for node_id in self.topological_order() {
+            let Some(node) = self.nodes.get(&node_id) else {
+                continue;
+            };
+            let deps = self.get_dependencies(node_id);
+            let parent_cx = deps
+                .first()
+                .and_then(|d| contexts.get(d))
+                .unwrap_or(&root_cx);
+
+            // Dependencies beyond the first become links (a node may join
+            // several upstream data flows).
+            let links: Vec<Link> = deps
+                .iter()
+                .skip(1)
+                .filter_map(|d| contexts.get(d))
+                .map(|cx| Link::new(cx.span().span_context().clone(), Vec::new(), 0))
+                .collect();
+
+            let mut span = SpanBuilder::from_name(node.skill_id.clone())
+                .with_links(links)
+                .start_with_context(tracer, parent_cx);
+
+            span.set_attribute(KeyValue::new("skill.id", node.skill_id.clone()));
+            span.set_attribute(KeyValue::new("agent.id", node.agent_id.clone()));
+            span.set_attribute(KeyValue::new("dtg.node.id", node.id.to_string()));
+            span.set_attribute(KeyValue::new(
+                "dtg.node.status",
+                format!("{:?}", node.status),
+            ));
+
+            // Resource usage recorded as a single structured event.
+            span.add_event(
+                "metrics",
+                vec![
+                    KeyValue::new("cpu_time_ms", node.metrics.cpu_time_ms as i64),
+                    KeyValue::new("memory_bytes", node.metrics.memory_bytes as i64),
+                    KeyValue::new("network_bytes", node.metrics.network_bytes as i64),
+                    KeyValue::new("disk_bytes", node.metrics.disk_bytes as i64),
+                    KeyValue::new("retry_count", node.metrics.retry_count as i64),
+                    KeyValue::new("quality_score", node.metrics.quality_score),
+                ],
+            );
+
+            match &node.error {
+                Some(error) => span.set_status(Status::error(error.clone())),
+                None => span.set_status(Status::Ok),
+            }
+
+            contexts.insert(node_id, opentelemetry::Context::current_with_span(span));
+        }
+
+        // Close all node spans, then the root; parentage is fixed by span
+        // context at creation, so end order only stamps end times.
+        for cx in contexts.values() {
+            cx.span().end();
+        }
+        root_cx.span().end();
+    }
+
+    /// Nodes in dependency order (Kahn's algorithm over the data-flow edges).
+    /// Any nodes left by a cycle are appended in arbitrary order so export is
+    /// total even for a malformed graph.
+    fn topological_order(&self) -> Vec<uuid::Uuid> {
+        let mut in_degree: std::collections::HashMap<uuid::Uuid, usize> = self
+            .nodes
+            .keys()
+            .map(|id| (*id, self.get_dependencies(*id).len()))
+            .collect();
+        let mut queue: std::collections::VecDeque<uuid::Uuid> = in_degree
+            .iter()
+            .filter(|(_, deg)| **deg == 0)
+            .map(|(id, _)| *id)
+            .collect();
+
+        let mut order = Vec::with_capacity(self.nodes.len());
+        while let Some(node_id) = queue.pop_front() {
+            order.push(node_id);
+            for dependent in self.get_dependents(node_id) {
+                if let Some(deg) = in_degree.get_mut(&dependent) {
+                    *deg = deg.saturating_sub(1);
+                    if *deg == 0 {
+                        queue.push_back(dependent);
+                    }
+                }
+            }
+        }
+
+        for id in self.nodes.keys() {
+            if !order.contains(id) {
+                order.push(*id);
+            }
+        }
+        order
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::dtg::DtgNode;
+
+    fn node(graph: &mut DataTransformationGraph) -> uuid::Uuid {
+        graph.add_node(DtgNode::new("skill".to_string(), "agent".to_string()))
+    }
+
+    fn index_of(order: &[uuid::Uuid], id: uuid::Uuid) -> usize {
+        order.iter().position(|n| *n == id).unwrap()
+    }
+
+    #[test]
+    fn test_topological_order_respects_a_linear_chain() {
+        let mut graph = DataTransformationGraph::new("linear".to_string());
+        let a = node(&mut graph);
+        let b = node(&mut graph);
+        let c = node(&mut graph);
+        graph.add_edge(a, b, uuid::Uuid::new_v4(), "data_flow".to_string());
+        graph.add_edge(b, c, uuid::Uuid::new_v4(), "data_flow".to_string());
+
+        let order = graph.topological_order();
+        assert_eq!(order.len(), 3);
+        assert!(index_of(&order, a) < index_of(&order, b));
+        assert!(index_of(&order, b) < index_of(&order, c));
+    }
+
+    #[test]
+    fn test_topological_order_waits_for_every_dependency_before_a_merge() {
+        let mut graph = DataTransformationGraph::new("merge".to_string());
+        let a = node(&mut graph);
+        let b = node(&mut graph);
+        let c = node(&mut graph);
+        graph.add_edge(a, c, uuid::Uuid::new_v4(), "data_flow".to_string());
+        graph.add_edge(b, c, uuid::Uuid::new_v4(), "data_flow".to_string());
+
+        let order = graph.topological_order();
+        assert_eq!(order.len(), 3);
+        assert!(index_of(&order, a) < index_of(&order, c));
+        assert!(index_of(&order, b) < index_of(&order, c));
+    }
+
+    #[test]
+    fn test_topological_order_still_includes_every_node_in_a_cycle() {
+        let mut graph = DataTransformationGraph::new("cyclic".to_string());
+        let a = node(&mut graph);
+        let b = node(&mut graph);
+        graph.add_edge(a, b, uuid::Uuid::new_v4(), "data_flow".to_string());
+        graph.add_edge(b, a, uuid::Uuid::new_v4(), "data_flow".to_string());
+
+        let order = graph.topological_order();
+        assert_eq!(order.len(), 2);
+        assert!(order.contains(&a));
+        assert!(order.contains(&b));
+    }
+}
@@ -39,28 +39,36 @@ fn create_ceo_agent() -> Agent {
     // Define CEO skills
     let strategic_decision_skill = AgentSkill {
         id: "strategic-decision".to_string(),
-        name: "Strategic Decision Making".to_string(),
-        description: "Makes final strategic decisions for the organization".to_string(),
+        name: "Strategic Decision Making".to_string().into(),
+        description: "Makes final strategic decisions for the organization".to_string().into(),
         tags: vec!["strategy".to_string(), "decision".to_string(), "leadership".to_string()],
-        examples: Some(vec![
-            "Approve new product development".to_string(),
-            "Make final budget allocation decisions".to_string(),
-        ]),
+        examples: Some(
+            vec![
+                "Approve new product development".to_string(),
+                "Make final budget allocation decisions".to_string(),
+            ]
+            .into(),
+        ),
         input_modes: Some(vec!["application/json".to_string()]),
         output_modes: Some(vec!["application/json".to_string()]),
+        security: None,
     };
-    
+
     let budget_approval_skill = AgentSkill {
         id: "budget-approval".to_string(),
-        name: "Budget Approval".to_string(),
-        description: "Approves budget allocations after CFO review".to_string(),
+        name: "Budget Approval".to_string().into(),
+        description: "Approves budget allocations after CFO review".to_string().into(),
         tags: vec!["budget".to_string(), "finance".to_string(), "approval".to_string()],
-        examples: Some(vec![
-            "Approve department budget requests".to_string(),
-            "Review and approve quarterly financial plans".to_string(),
-        ]),
+        examples: Some(
+            vec![
+                "Approve department budget requests".to_string(),
+                "Review and approve quarterly financial plans".to_string(),
+            ]
+            .into(),
+        ),
         input_modes: Some(vec!["application/json".to_string()]),
         output_modes: Some(vec!["application/json".to_string()]),
+        security: None,
     };
     
     // Define supported interfaces